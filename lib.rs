@@ -15,21 +15,28 @@ mod edgeware_bridge {
                         SpreadLayout,
                     }};
     use scale::{Decode, Encode};
-    use sha3::{Digest, Sha3_256};
+    use sha3::{Digest, Keccak256, Sha3_256};
 
     const ZERO_ADDRESS_BYTES: [u8; 32] = [0; 32];
     const ONE_DAY: u64 = 86400;
+    const STORAGE_VERSION: u16 = 6;
+    const DEFAULT_UPGRADE_DELAY: u64 = 2 * ONE_DAY;
+    const PROTOCOL_VERSION: u16 = 1;
+    const OUTBOUND_TREE_DEPTH: usize = 32;
+    const MAX_QUERY_RANGE: u128 = 100;
+    const BASIS_POINTS: u128 = 10000;
 
     #[ink(storage)]
     pub struct EdgewareBridge {
         swap_requests: StorageHashMap<Vec<u8>, Vec<AccountId>>,
         tokens: StorageHashMap<AccountId, bool>,
-        validators: StorageHashMap<AccountId, bool>,
+        validators: StorageHashMap<AccountId, ValidatorInfo>,
         daily_limit: StorageHashMap<AccountId, u128>,
         daily_spend: StorageHashMap<AccountId, u128>,
         daily_limit_set_time: StorageHashMap<AccountId, u64>,
         validator_rewards: StorageHashMap<AccountId, u128>,
-        fee: u128,
+        default_fee: FeeConfig,
+        fee_schedule: StorageHashMap<(AccountId, TransferDirection), FeeConfig>,
         signature_threshold: u16,
         max_validator_count: u16,
         tx_expiration_time: u64,
@@ -38,6 +45,53 @@ mod edgeware_bridge {
         token_contract: ERC20Token,
         chain_id: u8,
         min_amount_to_transfer: u128,
+        storage_version: u16,
+        migrated: bool,
+        upgrade_delay: u64,
+        pending_upgrade: Option<PendingUpgrade>,
+        successor: Option<AccountId>,
+        hash_scheme: HashScheme,
+        domain_chain_id: u64,
+        root_requests: StorageHashMap<Vec<u8>, Vec<AccountId>>,
+        approved_roots: StorageHashMap<Vec<u8>, u64>,
+        executed_swaps: StorageHashMap<Vec<u8>, bool>,
+        outbound_branch: Vec<Vec<u8>>,
+        outbound_leaf_count: u64,
+        treasury: AccountId,
+        treasury_share: u128,
+        treasury_balances: StorageHashMap<AccountId, u128>,
+        executed_nonces: StorageHashMap<(u64, u128), bool>,
+        contiguous_nonces: StorageHashMap<u64, u128>,
+        strict_nonce_ordering: bool,
+        destination_chain_id: u64,
+        outbound_transfers: StorageHashMap<u128, OutboundTransfer>,
+        relayer_bonus: u128,
+        public_relaying: bool,
+        liveness_period: u64,
+        quorum_numerator: u16,
+        quorum_denominator: u16,
+        min_transfer_amounts: StorageHashMap<AccountId, u128>,
+        max_transfer_amounts: StorageHashMap<AccountId, u128>,
+        remote_assets: StorageHashMap<(AccountId, u64), RemoteAsset>,
+        compliance_manager: AccountId,
+        blocked_accounts: StorageHashMap<AccountId, bool>,
+        blocked_receivers: StorageHashMap<String, bool>,
+        quarantined_swaps: StorageHashMap<Vec<u8>, SwapMessage>,
+        fee_tiers: StorageHashMap<AccountId, FeeTier>,
+        fee_side: FeeSide,
+        locked_liquidity: u128,
+        accrued_rewards: u128,
+        lp_shares: StorageHashMap<AccountId, u128>,
+        total_lp_shares: u128,
+        pool_liquidity: u128,
+        pool_borrowed: u128,
+        lp_fee_share: u128,
+        swap_variants: StorageHashMap<(u64, u128), Vec<Vec<u8>>>,
+        frozen_nonces: StorageHashMap<(u64, u128), bool>,
+        frozen_swaps: StorageHashMap<Vec<u8>, SwapMessage>,
+        freeze_conflicts: bool,
+        weight_threshold: u64,
+        weighted_rewards: bool,
     }
 
     /// Emitted when an user want to make cross chain transfer
@@ -46,9 +100,128 @@ mod edgeware_bridge {
         receiver: String,
         sender: AccountId,
         amount: u128,
+        fee: u128,
+        net_amount: u128,
         asset: AccountId,
         transfer_nonce: u128,
         timestamp: u64,
+        remote_asset: Vec<u8>,
+        remote_amount: u128,
+    }
+
+    /// Emitted when owner schedules migration to the successor contract
+    #[ink(event)]
+    pub struct UpgradeScheduled {
+        successor: AccountId,
+        execute_after: u64,
+    }
+
+    /// Emitted when owner cancels scheduled migration
+    #[ink(event)]
+    pub struct UpgradeCancelled {
+        successor: AccountId,
+    }
+
+    /// Emitted when locked coins are moved to the successor contract
+    #[ink(event)]
+    pub struct Upgraded {
+        successor: AccountId,
+        moved_balance: u128,
+        transfer_nonce: u128,
+    }
+
+    /// Emitted when validators approve Merkle root of the batch of inbound transfers
+    #[ink(event)]
+    pub struct RootApproved {
+        root: Vec<u8>,
+        timestamp: u64,
+    }
+
+    /// Emitted when a transfer of the approved batch is claimed with Merkle proof
+    #[ink(event)]
+    pub struct Claimed {
+        root: Vec<u8>,
+        message_hash: Vec<u8>,
+        receiver: AccountId,
+        amount: u128,
+        asset: AccountId,
+        transfer_nonce: u128,
+    }
+
+    /// Emitted when validator's key is replaced with a new one
+    #[ink(event)]
+    pub struct ValidatorRotated {
+        old_validator: AccountId,
+        new_validator: AccountId,
+    }
+
+    /// Emitted when validator is suspended, reactivated or announces its exit
+    #[ink(event)]
+    pub struct ValidatorStatusChanged {
+        validator: AccountId,
+        status: ValidatorStatus,
+    }
+
+    /// Emitted when approved swap to the blocked receiver is put in quarantine instead of being paid
+    #[ink(event)]
+    pub struct SwapQuarantined {
+        message_hash: Vec<u8>,
+        receiver: AccountId,
+    }
+
+    /// Emitted when quarantined swap is paid after its receiver was unblocked
+    #[ink(event)]
+    pub struct QuarantineReleased {
+        message_hash: Vec<u8>,
+        receiver: AccountId,
+    }
+
+    /// Emitted when liquidity provider deposits coins to the pool
+    #[ink(event)]
+    pub struct LiquidityProvided {
+        provider: AccountId,
+        amount: u128,
+        shares: u128,
+    }
+
+    /// Emitted when liquidity provider withdraws coins from the pool
+    #[ink(event)]
+    pub struct LiquidityWithdrawn {
+        provider: AccountId,
+        amount: u128,
+        shares: u128,
+    }
+
+    /// Emitted when validator revokes its approval of the pending swap
+    #[ink(event)]
+    pub struct ApprovalRevoked {
+        validator: AccountId,
+        message_hash: Vec<u8>,
+    }
+
+    /// Emitted when validators approve different messages of the same source transfer
+    #[ink(event)]
+    pub struct ConflictDetected {
+        source_chain_id: u64,
+        transfer_nonce: u128,
+        message_hashes: Vec<Vec<u8>>,
+        frozen: bool,
+    }
+
+    /// Emitted when owner resolves the conflict of swap messages
+    #[ink(event)]
+    pub struct ConflictResolved {
+        source_chain_id: u64,
+        transfer_nonce: u128,
+        accepted_hash: Option<Vec<u8>>,
+    }
+
+    /// Emitted when treasury withdraws its share of the collected fees
+    #[ink(event)]
+    pub struct TreasuryWithdrawal {
+        treasury: AccountId,
+        asset: AccountId,
+        amount: u128,
     }
 
     #[derive(Encode, Decode, SpreadLayout, PackedLayout, Clone)]
@@ -70,6 +243,238 @@ mod edgeware_bridge {
         pub amount: u128,
         pub asset: AccountId,
         pub transfer_nonce: u128,
+        pub source_chain_id: u64,
+    }
+
+    #[derive(Encode, Decode, SpreadLayout, PackedLayout, Clone)]
+    #[cfg_attr(
+        feature = "std",
+        derive(
+            Debug,
+            PartialEq,
+            Eq,
+            scale_info::TypeInfo,
+            ink_storage::traits::StorageLayout
+        )
+    )]
+    pub struct PendingUpgrade {
+        pub successor: AccountId,
+        pub execute_after: u64,
+    }
+
+    /// Hashing of swap messages, `AbiKeccak` matches `keccak256(abi.encodePacked(...))` on the Ethereum side
+    #[derive(Encode, Decode, SpreadLayout, PackedLayout, Clone, Copy, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(
+            Debug,
+            scale_info::TypeInfo,
+            ink_storage::traits::StorageLayout
+        )
+    )]
+    pub enum HashScheme {
+        ScaleSha3,
+        AbiKeccak,
+    }
+
+    /// Outbound transfer of this bridge, the amount is the one to release on the destination chain.
+    /// Remote amount is the same amount in decimals of the remote asset.
+    #[derive(Encode, Decode, SpreadLayout, PackedLayout, Clone)]
+    #[cfg_attr(
+        feature = "std",
+        derive(
+            Debug,
+            PartialEq,
+            Eq,
+            scale_info::TypeInfo,
+            ink_storage::traits::StorageLayout
+        )
+    )]
+    pub struct OutboundTransfer {
+        pub receiver: String,
+        pub sender: AccountId,
+        pub amount: u128,
+        pub asset: AccountId,
+        pub timestamp: u64,
+        pub destination_chain_id: u64,
+        pub remote_asset: Vec<u8>,
+        pub remote_amount: u128,
+    }
+
+    #[derive(Encode, Decode, SpreadLayout, PackedLayout, Clone)]
+    #[cfg_attr(
+        feature = "std",
+        derive(
+            Debug,
+            PartialEq,
+            Eq,
+            scale_info::TypeInfo,
+            ink_storage::traits::StorageLayout
+        )
+    )]
+    pub struct RemoteAsset {
+        pub remote_asset: Vec<u8>,
+        pub local_decimals: u8,
+        pub remote_decimals: u8,
+    }
+
+    #[derive(Encode, Decode, SpreadLayout, PackedLayout, Clone, Copy, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(
+            Debug,
+            scale_info::TypeInfo,
+            ink_storage::traits::StorageLayout
+        )
+    )]
+    pub enum ValidatorStatus {
+        Active,
+        Suspended,
+        Exiting,
+    }
+
+    #[derive(Encode, Decode, SpreadLayout, PackedLayout, Clone)]
+    #[cfg_attr(
+        feature = "std",
+        derive(
+            Debug,
+            PartialEq,
+            Eq,
+            scale_info::TypeInfo,
+            ink_storage::traits::StorageLayout
+        )
+    )]
+    pub struct ValidatorInfo {
+        pub name: String,
+        pub added_at: u64,
+        pub status: ValidatorStatus,
+        pub weight: u32,
+        pub approvals_count: u64,
+        pub last_seen: u64,
+    }
+
+    #[derive(Encode, Decode, SpreadLayout, PackedLayout, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    #[cfg_attr(
+        feature = "std",
+        derive(
+            Debug,
+            scale_info::TypeInfo,
+            ink_storage::traits::StorageLayout
+        )
+    )]
+    pub enum TransferDirection {
+        Outbound,
+        Inbound,
+    }
+
+    /// Fee in basis points of the transferred amount bounded by minimum and maximum fee
+    #[derive(Encode, Decode, SpreadLayout, PackedLayout, Clone, Copy)]
+    #[cfg_attr(
+        feature = "std",
+        derive(
+            Debug,
+            PartialEq,
+            Eq,
+            scale_info::TypeInfo,
+            ink_storage::traits::StorageLayout
+        )
+    )]
+    pub struct FeeConfig {
+        pub fee_bps: u16,
+        pub min_fee: u128,
+        pub max_fee: u128,
+    }
+
+    /// Fee tier of the account, discount is in basis points of the fee
+    #[derive(Encode, Decode, SpreadLayout, PackedLayout, Clone, Copy, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(
+            Debug,
+            scale_info::TypeInfo,
+            ink_storage::traits::StorageLayout
+        )
+    )]
+    pub enum FeeTier {
+        Exempt,
+        Discount(u16),
+    }
+
+    /// Side of the bridge which charges fee of the cross chain transfer
+    #[derive(Encode, Decode, SpreadLayout, PackedLayout, Clone, Copy, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(
+            Debug,
+            scale_info::TypeInfo,
+            ink_storage::traits::StorageLayout
+        )
+    )]
+    pub enum FeeSide {
+        Source,
+        Destination,
+        Both,
+    }
+
+    /// Native coin balance of the bridge compared with the coins it owes
+    #[derive(Encode, Decode, Clone)]
+    #[cfg_attr(
+        feature = "std",
+        derive(
+            Debug,
+            PartialEq,
+            Eq,
+            scale_info::TypeInfo
+        )
+    )]
+    pub struct SolvencyReport {
+        pub balance: u128,
+        pub locked_liquidity: u128,
+        pub accrued_rewards: u128,
+        pub treasury_balance: u128,
+        pub pool_liquidity: u128,
+        pub pool_borrowed: u128,
+        pub surplus: u128,
+        pub deficit: u128,
+    }
+
+    /// State which is moved from the old bridge to its successor through `export_state` and `migrate`
+    #[derive(Encode, Decode, Clone)]
+    #[cfg_attr(
+        feature = "std",
+        derive(
+            Debug,
+            PartialEq,
+            Eq,
+            scale_info::TypeInfo
+        )
+    )]
+    pub struct BridgeState {
+        pub storage_version: u16,
+        pub validators: Vec<AccountId>,
+        pub tokens: Vec<AccountId>,
+        pub daily_limits: Vec<(AccountId, u128)>,
+        pub validator_rewards: Vec<(AccountId, u128)>,
+        pub fee: u128,
+        pub signature_threshold: u16,
+        pub max_validator_count: u16,
+        pub tx_expiration_time: u64,
+        pub transfer_nonce: u128,
+        pub chain_id: u8,
+        pub min_amount_to_transfer: u128,
+        pub executed_nonces: Vec<(u64, u128)>,
+        pub contiguous_nonces: Vec<(u64, u128)>,
+        pub validator_infos: Vec<(AccountId, ValidatorInfo)>,
+        pub blocked_accounts: Vec<AccountId>,
+        pub blocked_receivers: Vec<String>,
+        pub quarantined_swaps: Vec<(Vec<u8>, SwapMessage)>,
+        pub treasury_balances: Vec<(AccountId, u128)>,
+        pub lp_shares: Vec<(AccountId, u128)>,
+        pub total_lp_shares: u128,
+        pub pool_liquidity: u128,
+        pub pool_borrowed: u128,
+        pub default_fee: FeeConfig,
+        pub fee_schedule: Vec<((AccountId, TransferDirection), FeeConfig)>,
     }
 
     impl EdgewareBridge {
@@ -83,6 +488,7 @@ mod edgeware_bridge {
             chain_id: u8,
             min_amount_to_transfer: u128
         ) -> Self {
+            assert!(transfer_fee < 100, "Fee should be between 0 and 100");
             let caller = Self::env().caller();
             let zero_address: AccountId = AccountId::from(ZERO_ADDRESS_BYTES);
             let mut daily_limit: StorageHashMap<AccountId, u128> = StorageHashMap::default();
@@ -92,12 +498,21 @@ mod edgeware_bridge {
             daily_limit_set_time.insert(zero_address, current_timestamp);
             let mut daily_spend: StorageHashMap<AccountId, u128> = StorageHashMap::default();
             daily_spend.insert(zero_address, 0);
+            let mut outbound_branch: Vec<Vec<u8>> = Vec::new();
+            for _ in 0..OUTBOUND_TREE_DEPTH {
+                outbound_branch.push(ZERO_ADDRESS_BYTES.to_vec());
+            }
             Self {
                 swap_requests: StorageHashMap::default(),
                 tokens: StorageHashMap::default(),
                 validators: StorageHashMap::default(),
                 daily_spend,
-                fee: transfer_fee,
+                default_fee: FeeConfig {
+                    fee_bps: (transfer_fee * 100) as u16,
+                    min_fee: 0,
+                    max_fee: u128::MAX,
+                },
+                fee_schedule: StorageHashMap::default(),
                 signature_threshold: threshold,
                 max_validator_count: max_permissible_validator_count,
                 tx_expiration_time: ONE_DAY,
@@ -109,6 +524,53 @@ mod edgeware_bridge {
                 token_contract,
                 chain_id,
                 min_amount_to_transfer,
+                storage_version: STORAGE_VERSION,
+                migrated: false,
+                upgrade_delay: DEFAULT_UPGRADE_DELAY,
+                pending_upgrade: None,
+                successor: None,
+                hash_scheme: HashScheme::ScaleSha3,
+                domain_chain_id: chain_id as u64,
+                root_requests: StorageHashMap::default(),
+                approved_roots: StorageHashMap::default(),
+                executed_swaps: StorageHashMap::default(),
+                outbound_branch,
+                outbound_leaf_count: 0,
+                treasury: caller,
+                treasury_share: 0,
+                treasury_balances: StorageHashMap::default(),
+                executed_nonces: StorageHashMap::default(),
+                contiguous_nonces: StorageHashMap::default(),
+                strict_nonce_ordering: false,
+                destination_chain_id: 0,
+                outbound_transfers: StorageHashMap::default(),
+                relayer_bonus: 0,
+                public_relaying: false,
+                liveness_period: 0,
+                quorum_numerator: 0,
+                quorum_denominator: 1,
+                min_transfer_amounts: StorageHashMap::default(),
+                max_transfer_amounts: StorageHashMap::default(),
+                remote_assets: StorageHashMap::default(),
+                compliance_manager: caller,
+                blocked_accounts: StorageHashMap::default(),
+                blocked_receivers: StorageHashMap::default(),
+                quarantined_swaps: StorageHashMap::default(),
+                fee_tiers: StorageHashMap::default(),
+                fee_side: FeeSide::Destination,
+                locked_liquidity: 0,
+                accrued_rewards: 0,
+                lp_shares: StorageHashMap::default(),
+                total_lp_shares: 0,
+                pool_liquidity: 0,
+                pool_borrowed: 0,
+                lp_fee_share: 0,
+                swap_variants: StorageHashMap::default(),
+                frozen_nonces: StorageHashMap::default(),
+                frozen_swaps: StorageHashMap::default(),
+                freeze_conflicts: false,
+                weight_threshold: 0,
+                weighted_rewards: false,
             }
         }
 
@@ -118,393 +580,3206 @@ mod edgeware_bridge {
             self.owner = new_owner;
         }
 
+        //  Sets default fee in percents, `set_default_fee` allows to set it in basis points with bounds
         #[ink(message)]
         pub fn set_fee(&mut self, new_fee: u128) {
             self.ensure_owner(self.env().caller());
-            assert!(new_fee > 0 || new_fee < 100, "Fee should be between 0 and 100");
-            self.fee = new_fee;
+            assert!(new_fee < 100, "Fee should be between 0 and 100");
+            self.default_fee.fee_bps = (new_fee * 100) as u16;
         }
 
         #[ink(message)]
-        pub fn add_validator(&mut self, new_validator: AccountId) {
+        pub fn set_default_fee(&mut self, fee_bps: u16, min_fee: u128, max_fee: u128) {
             self.ensure_owner(self.env().caller());
-            let count_acrive_validators: u16 = self.validators.len() as u16;
-            assert!(count_acrive_validators + 1 <= self.max_validator_count, "Count of Validators already reach maximum");
-            self.validators.insert(new_validator, true);
+            self.default_fee = self.validate_fee_config(fee_bps, min_fee, max_fee);
         }
 
         #[ink(message)]
-        pub fn remove_validator(&mut self, validator: AccountId) {
+        pub fn set_asset_fee(&mut self, asset: AccountId, direction: TransferDirection, fee_bps: u16, min_fee: u128, max_fee: u128) {
             self.ensure_owner(self.env().caller());
-            assert!((self.validators.len() - 1) as u16 >= self.signature_threshold, "Count of Validators can't be less than necessary threshold of approvals");
-            assert_eq!(self.validators.take(&validator).is_some(), true);
+            assert!(self.check_asset(&asset), "Unknown asset");
+            let fee_config: FeeConfig = self.validate_fee_config(fee_bps, min_fee, max_fee);
+            self.fee_schedule.insert((asset, direction), fee_config);
         }
 
+        //  Source side charges outbound transfers of this bridge, destination side charges swaps paid by it
         #[ink(message)]
-        pub fn set_threshold(&mut self, new_signature_threshold: u16) {
+        pub fn set_fee_side(&mut self, new_fee_side: FeeSide) {
             self.ensure_owner(self.env().caller());
-            assert!(new_signature_threshold > 0 && new_signature_threshold <= self.max_validator_count, "Signature threshold must be more than zero and less or equal maximum validators count");
-            self.signature_threshold = new_signature_threshold;
+            self.fee_side = new_fee_side;
         }
 
+        //  Share of native coin fees in percents which goes to liquidity providers
         #[ink(message)]
-        pub fn add_token(&mut self, new_token: AccountId, token_daily_limit: u128) {
+        pub fn set_lp_fee_share(&mut self, new_lp_fee_share: u128) {
             self.ensure_owner(self.env().caller());
-            self.tokens.insert(new_token, true);
-            assert!(token_daily_limit > 0, "Token daily limit must be more than zero");
-            self.daily_limit.insert(new_token, token_daily_limit);
-            self.daily_limit_set_time.insert(new_token, self.env().block_timestamp() / 1000);
-            self.daily_spend.insert(new_token, 0);
+            assert!(new_lp_fee_share <= 100, "Liquidity providers share should be between 0 and 100");
+            self.lp_fee_share = new_lp_fee_share;
         }
 
         #[ink(message)]
-        pub fn remove_token(&mut self, token: AccountId) {
+        pub fn set_fee_tier(&mut self, account: AccountId, tier: FeeTier) {
             self.ensure_owner(self.env().caller());
-            assert_eq!(self.tokens.take(&token).is_some(), true);
-            assert_eq!(self.daily_limit.take(&token).is_some(), true);
-            assert_eq!(self.daily_limit_set_time.take(&token).is_some(), true);
+            if let FeeTier::Discount(discount_bps) = tier {
+                assert!(discount_bps > 0 && (discount_bps as u128) < BASIS_POINTS, "Discount should be between 0 and 10000 basis points");
+            }
+            self.fee_tiers.insert(account, tier);
         }
 
         #[ink(message)]
-        pub fn set_daily_limit(&mut self, new_limit: u128, asset_limited: AccountId) {
+        pub fn remove_fee_tier(&mut self, account: AccountId) {
             self.ensure_owner(self.env().caller());
-            assert_eq!(self.tokens.contains_key(&asset_limited), true);
-            assert!(new_limit > 0, "Daily limit must be more than zero");
-            self.daily_limit.insert(asset_limited, new_limit);
+            assert!(self.fee_tiers.take(&account).is_some(), "Account doesn't have fee tier");
         }
 
+        //  Asset falls back to the default fee after removal of its own fee
         #[ink(message)]
-        pub fn set_tx_expiration_time(&mut self, new_tx_expiration_time: u64) {
+        pub fn remove_asset_fee(&mut self, asset: AccountId, direction: TransferDirection) {
             self.ensure_owner(self.env().caller());
-            assert!(new_tx_expiration_time > 0, "Transaction expiration time must be greater than zero");
-            self.tx_expiration_time = new_tx_expiration_time;
+            assert!(self.fee_schedule.take(&(asset, direction)).is_some(), "Asset doesn't have its own fee");
         }
 
         #[ink(message)]
-        pub fn is_token_in(&self, token: AccountId) -> bool {
-            self.tokens.contains_key(&token)
+        pub fn set_treasury(&mut self, new_treasury: AccountId) {
+            self.ensure_owner(self.env().caller());
+            self.treasury = new_treasury;
         }
 
+        //  Share of the collected fees in percents which goes to treasury instead of validators
         #[ink(message)]
-        pub fn is_validator_in(&self, validator: AccountId) -> bool {
-            self.validators.contains_key(&validator)
+        pub fn set_treasury_share(&mut self, new_treasury_share: u128) {
+            self.ensure_owner(self.env().caller());
+            assert!(new_treasury_share <= 100, "Treasury share should be between 0 and 100");
+            self.treasury_share = new_treasury_share;
         }
 
+        //  Share of the swap fee in percents which goes to the account that triggers execution of the swap
         #[ink(message)]
-        pub fn is_swap_request_in(&self, swap_hash: Vec<u8>) -> bool {
-            self.swap_requests.contains_key(&swap_hash)
+        pub fn set_relayer_bonus(&mut self, new_relayer_bonus: u128) {
+            self.ensure_owner(self.env().caller());
+            assert!(new_relayer_bonus <= 100, "Relayer bonus should be between 0 and 100");
+            self.relayer_bonus = new_relayer_bonus;
         }
 
+        //  When public relaying is on, validators only collect approvals and anyone can execute
+        //  the approved swap through `execute_swap`
         #[ink(message)]
-        pub fn get_daily_limit(&self, token: AccountId) -> u128 {
-            self.daily_limit.get(&token).unwrap().clone()
+        pub fn set_public_relaying(&mut self, is_public: bool) {
+            self.ensure_owner(self.env().caller());
+            self.public_relaying = is_public;
         }
 
         #[ink(message)]
-        pub fn get_daily_spend(&self, token: AccountId) -> u128 {
-            self.daily_spend.get(&token).unwrap().clone()
+        pub fn set_compliance_manager(&mut self, new_compliance_manager: AccountId) {
+            self.ensure_owner(self.env().caller());
+            self.compliance_manager = new_compliance_manager;
         }
 
         #[ink(message)]
-        pub fn get_daily_limit_set_time(&self, token: AccountId) -> u64 {
-            self.daily_limit_set_time.get(&token).unwrap().clone()
+        pub fn block_account(&mut self, account: AccountId) {
+            self.ensure_compliance(self.env().caller());
+            self.blocked_accounts.insert(account, true);
         }
 
         #[ink(message)]
-        pub fn get_fee(&self) -> u128 {
-            self.fee
+        pub fn unblock_account(&mut self, account: AccountId) {
+            self.ensure_compliance(self.env().caller());
+            assert!(self.blocked_accounts.take(&account).is_some(), "Account isn't blocked");
         }
 
+        //  Blocks receiver's address on the other chain
         #[ink(message)]
-        pub fn get_signature_threshold(&self) -> u16 {
-            self.signature_threshold
+        pub fn block_receiver(&mut self, receiver: String) {
+            self.ensure_compliance(self.env().caller());
+            self.blocked_receivers.insert(self.normalize_receiver(&receiver), true);
         }
 
         #[ink(message)]
-        pub fn get_max_validator_count(&self) -> u16 {
-            self.max_validator_count
+        pub fn unblock_receiver(&mut self, receiver: String) {
+            self.ensure_compliance(self.env().caller());
+            assert!(self.blocked_receivers.take(&self.normalize_receiver(&receiver)).is_some(), "Receiver isn't blocked");
         }
 
+        //  Pays quarantined swap after its receiver was unblocked
         #[ink(message)]
-        pub fn get_tx_expiration_time(&self) -> u64 {
-            self.tx_expiration_time
+        pub fn release_quarantined_swap(&mut self, message_hash: Vec<u8>) {
+            self.ensure_compliance(self.env().caller());
+            self.ensure_not_upgraded();
+            let transfer_info: SwapMessage = self.quarantined_swaps.take(&message_hash).expect("Swap isn't in quarantine");
+            assert!(!self.is_account_blocked(transfer_info.receiver), "Receiver is still blocked");
+            let amount: u128 = self.to_local_amount(&transfer_info);
+            self.make_swap(transfer_info.asset, amount, transfer_info.receiver, None);
+            self.env().emit_event(QuarantineReleased {
+                message_hash,
+                receiver: transfer_info.receiver,
+            });
         }
 
         #[ink(message)]
-        pub fn get_transfer_nonce(&self) -> u128 {
-            self.transfer_nonce
+        pub fn add_validator(&mut self, new_validator: AccountId) {
+            self.ensure_owner(self.env().caller());
+            let count_acrive_validators: u16 = self.validators.len() as u16;
+            assert!(count_acrive_validators + 1 <= self.max_validator_count, "Count of Validators already reach maximum");
+            assert!(!self.validators.contains_key(&new_validator), "Validator already exists");
+            self.validators.insert(new_validator, self.new_validator_info());
         }
 
         #[ink(message)]
-        pub fn get_tokens(&self) -> Vec<AccountId> {
-            let mut tokens: Vec<AccountId> = Vec::new();
-            for el in self.tokens.keys() {
-                tokens.push(el.clone());
-            }
-            tokens
+        pub fn set_validator_name(&mut self, validator: AccountId, name: String) {
+            let caller: AccountId = self.env().caller();
+            assert!(caller == validator || caller == self.owner, "Validator's name can be changed only by the validator or owner");
+            let validator_info: &mut ValidatorInfo = self.validators.get_mut(&validator).expect("Unknown validator");
+            validator_info.name = name;
         }
 
         #[ink(message)]
-        pub fn get_validators(&self) -> Vec<AccountId> {
-            let mut validators: Vec<AccountId> = Vec::new();
-            for el in self.validators.keys() {
-                validators.push(el.clone());
-            }
-            validators
+        pub fn set_validator_weight(&mut self, validator: AccountId, weight: u32) {
+            self.ensure_owner(self.env().caller());
+            assert!(weight > 0, "Validator's weight must be more than zero");
+            let validator_info: &mut ValidatorInfo = self.validators.get_mut(&validator).expect("Unknown validator");
+            validator_info.weight = weight;
+            self.ensure_weight_threshold_reachable(None);
         }
 
+        //  When weight threshold is set, swaps and roots are executed once the summed weight of approvers
+        //  reaches it instead of the count of approvals. Zero threshold switches back to counting approvals.
         #[ink(message)]
-        pub fn get_chain_id(&self) -> u8 {
-            self.chain_id
+        pub fn set_weight_threshold(&mut self, new_weight_threshold: u64) {
+            self.ensure_owner(self.env().caller());
+            assert!(new_weight_threshold <= self.get_active_weight(None), "Weight threshold can't be greater than total weight of active validators");
+            self.weight_threshold = new_weight_threshold;
         }
 
+        //  Distributes validators' rewards proportionally to their weights
         #[ink(message)]
-        pub fn get_request_swaps(&self) -> Vec<Vec<u8>> {
-            let mut swaps: Vec<Vec<u8>> = Vec::new();
-            for el in self.swap_requests.keys() {
-                swaps.push(el.clone());
-            }
-            swaps
+        pub fn set_weighted_rewards(&mut self, is_weighted: bool) {
+            self.ensure_owner(self.env().caller());
+            self.weighted_rewards = is_weighted;
         }
 
+        //  Suspended validator stays in the list, but can't approve swaps and doesn't get rewards
         #[ink(message)]
-        pub fn get_count_of_approvals(&self, message_hash: Vec<u8>) -> u16 {
-            let validators_who_approved_swap: Option<Vec<AccountId>> = self.get_validators_who_approved(&message_hash);
-            match validators_who_approved_swap {
-                Some(n) => {
-                    return n.len() as u16;
-                },
-                None => {
-                    return 0;
-                }
-            }
+        pub fn suspend_validator(&mut self, validator: AccountId) {
+            self.ensure_owner(self.env().caller());
+            self.ensure_signature_threshold_reachable(&validator);
+            self.set_validator_status(validator, ValidatorStatus::Suspended);
         }
 
         #[ink(message)]
-        pub fn get_validator_rewards(&self, validator: AccountId) -> u128 {
-            let rewards_amount: Option<&u128> = self.validator_rewards.get(&validator);
-
-            match rewards_amount {
-                Some(rew) => return *rew,
-                None => return 0,
-            }
+        pub fn reactivate_validator(&mut self, validator: AccountId) {
+            self.ensure_owner(self.env().caller());
+            self.set_validator_status(validator, ValidatorStatus::Active);
         }
 
-        //  This method necessary such as there can be not relevant and did not approved requests which will take storage
-        //  and to clean the storage and save some money owner can use this method
+        //  Validators who didn't approve anything or send heartbeat during the liveness period
+        //  don't get rewards and aren't counted in quorum. Zero period disables liveness tracking.
         #[ink(message)]
-        pub fn clean_request_swaps_list(&mut self) {
+        pub fn set_liveness_period(&mut self, new_liveness_period: u64) {
             self.ensure_owner(self.env().caller());
-            self.swap_requests = StorageHashMap::default();
+            self.liveness_period = new_liveness_period;
         }
 
+        // Validator method
         #[ink(message)]
-        pub fn request_rewards(&mut self) {
+        pub fn heartbeat(&mut self) {
             let caller: AccountId = self.env().caller();
-            let rewards_amount: Option<&u128> = self.validator_rewards.get(&caller);
-            assert!(rewards_amount.is_some(), "This address doesn't have any rewards");
+            let current_time: u64 = self.env().block_timestamp() / 1000;
+            let validator_info: &mut ValidatorInfo = self.validators.get_mut(&caller).expect("Only Validator can send heartbeat");
+            validator_info.last_seen = current_time;
+        }
 
-            assert!(self.env().transfer(caller, *rewards_amount.unwrap()).is_ok(), "Error while transfer rewards to the validator");
+        //  Validator announces that it's going to leave, owner has to remove it afterwards
+        #[ink(message)]
+        pub fn announce_exit(&mut self) {
+            let caller: AccountId = self.env().caller();
+            self.set_validator_status(caller, ValidatorStatus::Exiting);
+        }
 
-            self.validator_rewards.take(&caller);
+        #[ink(message)]
+        pub fn remove_validator(&mut self, validator: AccountId) {
+            self.ensure_owner(self.env().caller());
+            self.ensure_signature_threshold_reachable(&validator);
+            self.ensure_weight_threshold_reachable(Some(&validator));
+            assert_eq!(self.validators.take(&validator).is_some(), true);
         }
 
-        // Validator method
+        //  Replaces validator's key keeping its rewards and approvals of pending swaps and roots
         #[ink(message)]
-        pub fn request_swap(&mut self, transfer_info: SwapMessage) {
+        pub fn rotate_validator(&mut self, old_validator: AccountId, new_validator: AccountId) {
             let caller: AccountId = self.env().caller();
-            assert!(self.validators.get(&caller).is_some(), "Only Validator can send requests to swap assets");
+            assert!(caller == old_validator || caller == self.owner, "Validator's key can be rotated only by the validator or owner");
+            assert!(!self.validators.contains_key(&new_validator), "New key already belongs to a validator");
+            let validator_info = self.validators.take(&old_validator).expect("Unknown validator");
+            self.validators.insert(new_validator, validator_info);
+
+            if let Some(rewards) = self.validator_rewards.take(&old_validator) {
+                let existing_rewards: u128 = self.get_validator_rewards(new_validator);
+                self.validator_rewards.insert(new_validator, existing_rewards + rewards);
+            }
 
-            assert!(transfer_info.chain_id == self.chain_id, "Swap request's chain ID doesn't match contract's chain ID");
+            for (_, approvals) in self.swap_requests.iter_mut() {
+                Self::replace_approver(approvals, &old_validator, &new_validator);
+            }
+            for (_, approvals) in self.root_requests.iter_mut() {
+                Self::replace_approver(approvals, &old_validator, &new_validator);
+            }
 
-            assert!(self.check_expiration_time(transfer_info.timestamp.clone()), "Transaction can't be sent because of expiration time");
+            self.env().emit_event(ValidatorRotated {
+                old_validator,
+                new_validator,
+            });
+        }
 
-            assert!(self.check_asset(&transfer_info.asset), "Unknown asset is trying to transfer");
+        #[ink(message)]
+        pub fn set_threshold(&mut self, new_signature_threshold: u16) {
+            self.ensure_owner(self.env().caller());
+            assert!(new_signature_threshold > 0 && new_signature_threshold <= self.max_validator_count, "Signature threshold must be more than zero and less or equal maximum validators count");
+            self.signature_threshold = new_signature_threshold;
+        }
 
-            let message_hash: Vec<u8> = self.hash_message(transfer_info.clone());
+        //  Makes threshold equal to numerator / denominator of active validators plus one,
+        //  the absolute signature threshold is used as the lower bound. Zero numerator disables relative quorum.
+        #[ink(message)]
+        pub fn set_quorum_ratio(&mut self, numerator: u16, denominator: u16) {
+            self.ensure_owner(self.env().caller());
+            assert!(denominator > 0, "Quorum denominator must be more than zero");
+            assert!(numerator < denominator, "Quorum ratio must be less than one");
+            self.quorum_numerator = numerator;
+            self.quorum_denominator = denominator;
+        }
 
-            let validators_who_approved_swap: Option<Vec<AccountId>> = self.get_validators_who_approved(&message_hash);
-            match validators_who_approved_swap {
-                Some(n) => {
-                    assert!(self.is_in(&n, &caller) == false, "This Validator has already sent approval");
-                    if (n.len() as u16) + 1 >= self.signature_threshold {
-                        self.make_swap(transfer_info.asset, transfer_info.amount, transfer_info.receiver);
-                        self.swap_requests.take(&message_hash);
-                    } else {
-                        let mut updated_validator_list: Vec<AccountId> = n.clone();
-                        updated_validator_list.push(caller);
-                        self.swap_requests.insert(message_hash, updated_validator_list);
-                    }
-                },
-                None => {
-                    let mut init_vec_of_validators: Vec<AccountId> = Vec::new();
-                    init_vec_of_validators.push(caller);
-                    self.swap_requests.insert(message_hash, init_vec_of_validators);
-                }
-            }
+        #[ink(message)]
+        pub fn add_token(&mut self, new_token: AccountId, token_daily_limit: u128) {
+            self.ensure_owner(self.env().caller());
+            self.tokens.insert(new_token, true);
+            assert!(token_daily_limit > 0, "Token daily limit must be more than zero");
+            self.daily_limit.insert(new_token, token_daily_limit);
+            self.daily_limit_set_time.insert(new_token, self.env().block_timestamp() / 1000);
+            self.daily_spend.insert(new_token, 0);
         }
 
-        // User method
-        #[ink(message, payable)]
-        pub fn transfer_coin(&mut self, receiver: String) -> bool {
-            let attached_deposit: u128 = self.env().transferred_balance();
-            
-            assert!(attached_deposit >= self.min_amount_to_transfer, "Transfer amount should be bigger or equal than minimum required amount");
+        #[ink(message)]
+        pub fn remove_token(&mut self, token: AccountId) {
+            self.ensure_owner(self.env().caller());
+            assert_eq!(self.tokens.take(&token).is_some(), true);
+            assert_eq!(self.daily_limit.take(&token).is_some(), true);
+            assert_eq!(self.daily_limit_set_time.take(&token).is_some(), true);
+        }
 
-            let zero_address: AccountId = AccountId::from(ZERO_ADDRESS_BYTES);
+        #[ink(message)]
+        pub fn set_daily_limit(&mut self, new_limit: u128, asset_limited: AccountId) {
+            self.ensure_owner(self.env().caller());
+            assert_eq!(self.tokens.contains_key(&asset_limited), true);
+            assert!(new_limit > 0, "Daily limit must be more than zero");
+            self.daily_limit.insert(asset_limited, new_limit);
+        }
 
-            self.check_asset_daily_limit(&zero_address, attached_deposit);
+        //  In strict mode inbound transfers from every source chain have to be executed in order of their nonces
+        #[ink(message)]
+        pub fn set_strict_nonce_ordering(&mut self, is_strict: bool) {
+            self.ensure_owner(self.env().caller());
+            self.strict_nonce_ordering = is_strict;
+        }
 
-            self.increase_transfer_nonce();
+        #[ink(message)]
+        pub fn set_destination_chain_id(&mut self, new_destination_chain_id: u64) {
+            self.ensure_owner(self.env().caller());
+            self.destination_chain_id = new_destination_chain_id;
+        }
 
-            self.distribute_rewards_for_validators(attached_deposit);
+        //  Minimum amount for assets which don't have their own transfer limits
+        #[ink(message)]
+        pub fn set_min_amount_to_transfer(&mut self, new_min_amount: u128) {
+            self.ensure_owner(self.env().caller());
+            self.min_amount_to_transfer = new_min_amount;
+        }
 
-            self.env().emit_event(Transfer {
-                receiver,
-                sender: self.env().caller(),
-                amount: attached_deposit,
-                asset: zero_address,
-                transfer_nonce: self.transfer_nonce,
-                timestamp: self.env().block_timestamp() / 1000,
-            });
-            true
+        #[ink(message)]
+        pub fn set_transfer_limits(&mut self, asset: AccountId, min_amount: u128, max_amount: u128) {
+            self.ensure_owner(self.env().caller());
+            assert!(self.check_asset(&asset), "Unknown asset");
+            assert!(max_amount > 0, "Maximum transfer amount must be more than zero");
+            assert!(min_amount <= max_amount, "Minimum transfer amount can't be greater than maximum");
+            self.min_transfer_amounts.insert(asset, min_amount);
+            self.max_transfer_amounts.insert(asset, max_amount);
         }
 
-        // User method
         #[ink(message)]
-        pub fn transfer_token(&mut self, receiver: String, amount: u128, asset: AccountId) -> bool {
-            assert!(amount >= self.min_amount_to_transfer, "Transfer amount should be bigger or equal than minimum required amount");
-            assert!(self.check_asset(&asset), "Unknown asset is trying to transfer");
-            self.check_asset_daily_limit(&asset, amount);
-            let caller: AccountId = self.env().caller();
-            assert!(self.token_contract.balance_of(caller) >= amount, "Sender doesn't have enough tokens to make transfer");
-            assert!(self.token_contract.burn(amount.clone(), caller), "Error while burn sender's tokens");
-            self.increase_transfer_nonce();
+        pub fn remove_transfer_limits(&mut self, asset: AccountId) {
+            self.ensure_owner(self.env().caller());
+            assert!(self.min_transfer_amounts.take(&asset).is_some(), "Asset doesn't have its own transfer limits");
+            self.max_transfer_amounts.take(&asset);
+        }
 
-            self.env().emit_event(Transfer {
-                receiver,
-                sender: self.env().caller(),
-                amount,
-                asset,
-                transfer_nonce: self.transfer_nonce,
-                timestamp: self.env().block_timestamp() / 1000,
+        #[ink(message)]
+        pub fn register_remote_asset(&mut self, asset: AccountId, remote_chain_id: u64, remote_asset: Vec<u8>, local_decimals: u8, remote_decimals: u8) {
+            self.ensure_owner(self.env().caller());
+            assert!(self.check_asset(&asset), "Unknown asset");
+            assert!(remote_asset.len() > 0, "Remote asset identifier can't be empty");
+            let decimals_difference: u32 = if local_decimals > remote_decimals {
+                (local_decimals - remote_decimals) as u32
+            } else {
+                (remote_decimals - local_decimals) as u32
+            };
+            assert!(10u128.checked_pow(decimals_difference).is_some(), "Difference of decimals is too big");
+            self.remote_assets.insert((asset, remote_chain_id), RemoteAsset {
+                remote_asset,
+                local_decimals,
+                remote_decimals,
             });
-            true
         }
 
-        fn distribute_rewards_for_validators(&mut self, amount: u128) {
-            let rewards_amount: u128 = (amount * self.fee) / 100;
-
-            for (validator_address, is_validator) in self.validators.iter() {
-                if *is_validator {
-                    let existing_rewards = self.validator_rewards.get(validator_address);
-                    match existing_rewards {
-                        Some(rew) => {
-                            let updated_reward: u128 = rew + (rewards_amount / self.validators.len() as u128);
-                            self.validator_rewards.insert(validator_address.clone(), updated_reward);
-                        },
-                        None => {
-                            let reward: u128 = rewards_amount / self.validators.len() as u128;
-                            self.validator_rewards.insert(validator_address.clone(), reward);
-                        }
-                    }
-                }
-            }
+        #[ink(message)]
+        pub fn remove_remote_asset(&mut self, asset: AccountId, remote_chain_id: u64) {
+            self.ensure_owner(self.env().caller());
+            assert!(self.remote_assets.take(&(asset, remote_chain_id)).is_some(), "Asset isn't registered for this chain");
         }
 
-        fn check_asset_daily_limit(&mut self, asset: &AccountId, amount: u128) {
-            let asset_daily_limit: u128 = self.daily_limit.get(asset).unwrap().clone();
-
-            assert!(asset_daily_limit > 0, "Can't transfer asset without daily limit");
+        #[ink(message)]
+        pub fn set_tx_expiration_time(&mut self, new_tx_expiration_time: u64) {
+            self.ensure_owner(self.env().caller());
+            assert!(new_tx_expiration_time > 0, "Transaction expiration time must be greater than zero");
+            self.tx_expiration_time = new_tx_expiration_time;
+        }
 
-            self.update_daily_limit(asset);
+        #[ink(message)]
+        pub fn is_token_in(&self, token: AccountId) -> bool {
+            self.tokens.contains_key(&token)
+        }
 
-            let asset_daily_spent: u128 = self.daily_spend.get(asset).unwrap().clone();
+        #[ink(message)]
+        pub fn is_validator_in(&self, validator: AccountId) -> bool {
+            self.validators.contains_key(&validator)
+        }
 
-            assert!(amount + asset_daily_spent <= asset_daily_limit);
+        #[ink(message)]
+        pub fn get_validator_info(&self, validator: AccountId) -> Option<ValidatorInfo> {
+            self.validators.get(&validator).cloned()
+        }
 
-            self.daily_spend.insert(*asset, asset_daily_spent + amount);
+        #[ink(message)]
+        pub fn get_liveness_period(&self) -> u64 {
+            self.liveness_period
         }
 
-        fn get_validators_who_approved(&self, message_hash: &Vec<u8>) -> Option<Vec<AccountId>> {
-            let validators: Option<&Vec<AccountId>> = self.swap_requests.get(message_hash);
-            match validators {
-                Some(n) => return Some(n.clone()),
-                None => return None,
+        #[ink(message)]
+        pub fn is_validator_live(&self, validator: AccountId) -> bool {
+            match self.validators.get(&validator) {
+                Some(validator_info) => self.is_live(validator_info),
+                None => false,
             }
         }
 
-        fn is_in(&self, list_of_accs: &Vec<AccountId>, new_acc: &AccountId) -> bool {
-            for acc in list_of_accs.iter() {
-                if acc == new_acc {
-                    return true;
-                }
-            }
-            return false;
+        #[ink(message)]
+        pub fn is_swap_request_in(&self, swap_hash: Vec<u8>) -> bool {
+            self.swap_requests.contains_key(&swap_hash)
         }
 
-        fn increase_transfer_nonce(&mut self) {
-            self.transfer_nonce = self.transfer_nonce + 1;
+        #[ink(message)]
+        pub fn get_daily_limit(&self, token: AccountId) -> u128 {
+            self.daily_limit.get(&token).unwrap().clone()
         }
 
-        fn check_asset(&self, asset_address: &AccountId) -> bool {
-            let zero_address: AccountId = AccountId::from(ZERO_ADDRESS_BYTES);
-            if asset_address == &zero_address || self.tokens.contains_key(asset_address) {
-                return true;
-            } else {
-                return false;
-            }
+        #[ink(message)]
+        pub fn get_daily_spend(&self, token: AccountId) -> u128 {
+            self.daily_spend.get(&token).unwrap().clone()
         }
 
-        fn hash_message(&self, swap_message: SwapMessage) -> Vec<u8> {
-            let encoded: Vec<u8> = swap_message.encode();
-            let mut hasher = Sha3_256::new();
-            hasher.input(encoded.as_slice());
-            let result = hasher.result();
-            result.to_vec()
+        #[ink(message)]
+        pub fn get_daily_limit_set_time(&self, token: AccountId) -> u64 {
+            self.daily_limit_set_time.get(&token).unwrap().clone()
         }
 
+        //  Returns default fee in whole percents, fractions of a percent set through `set_default_fee`
+        //  are truncated, use `get_default_fee` to get the exact fee in basis points
+        #[ink(message)]
+        pub fn get_fee(&self) -> u128 {
+            self.default_fee.fee_bps as u128 / 100
+        }
 
+        #[ink(message)]
+        pub fn get_default_fee(&self) -> FeeConfig {
+            self.default_fee
+        }
 
-        fn check_expiration_time(&self, tx_time: u64) -> bool {
-            let current_time: u64 = self.env().block_timestamp() / 1000;
-            
-            if current_time - tx_time > self.tx_expiration_time {
-                return false;
-            } else {
-                return true;
-            }
+        #[ink(message)]
+        pub fn get_asset_fee(&self, asset: AccountId, direction: TransferDirection) -> FeeConfig {
+            self.fee_schedule.get(&(asset, direction)).copied().unwrap_or(self.default_fee)
         }
 
-        fn update_daily_limit(&mut self, asset: &AccountId) {
-            let current_time: u64 = self.env().block_timestamp() / 1000;
+        #[ink(message)]
+        pub fn get_fee_side(&self) -> FeeSide {
+            self.fee_side
+        }
 
-            let last_limit_set_time: &u64 = self.daily_limit_set_time.get(asset).unwrap();
-            if current_time - last_limit_set_time > ONE_DAY {
+        #[ink(message)]
+        pub fn get_fee_tier(&self, account: AccountId) -> Option<FeeTier> {
+            self.fee_tiers.get(&account).copied()
+        }
+
+        //  Returns fee and amount which will be sent to the other chain after outbound transfer of the caller,
+        //  dust below precision of the remote asset is included in the fee as it's kept by the bridge
+        #[ink(message)]
+        pub fn quote_fee(&self, asset: AccountId, amount: u128) -> (u128, u128) {
+            let fee_amount: u128 = self.compute_fee(asset, TransferDirection::Outbound, amount, self.env().caller());
+            let (_, _, dust) = self.to_remote_amount(&asset, amount - fee_amount);
+            (fee_amount + dust, amount - fee_amount - dust)
+        }
+
+        #[ink(message)]
+        pub fn get_treasury(&self) -> AccountId {
+            self.treasury
+        }
+
+        #[ink(message)]
+        pub fn get_treasury_share(&self) -> u128 {
+            self.treasury_share
+        }
+
+        #[ink(message)]
+        pub fn get_treasury_balance(&self, asset: AccountId) -> u128 {
+            self.treasury_balances.get(&asset).copied().unwrap_or(0)
+        }
+
+        #[ink(message)]
+        pub fn get_relayer_bonus(&self) -> u128 {
+            self.relayer_bonus
+        }
+
+        #[ink(message)]
+        pub fn is_public_relaying(&self) -> bool {
+            self.public_relaying
+        }
+
+        //  Returns count of approvals necessary to execute swap at the moment
+        #[ink(message)]
+        pub fn get_signature_threshold(&self) -> u16 {
+            if self.quorum_numerator == 0 {
+                return self.signature_threshold;
+            }
+            let active_validators_count: u32 = self.get_active_validators_count() as u32;
+            let relative_threshold: u16 = (active_validators_count * self.quorum_numerator as u32 / self.quorum_denominator as u32 + 1) as u16;
+            if relative_threshold > self.signature_threshold {
+                relative_threshold
+            } else {
+                self.signature_threshold
+            }
+        }
+
+        #[ink(message)]
+        pub fn get_min_signature_threshold(&self) -> u16 {
+            self.signature_threshold
+        }
+
+        #[ink(message)]
+        pub fn get_quorum_ratio(&self) -> (u16, u16) {
+            (self.quorum_numerator, self.quorum_denominator)
+        }
+
+        #[ink(message)]
+        pub fn get_max_validator_count(&self) -> u16 {
+            self.max_validator_count
+        }
+
+        #[ink(message)]
+        pub fn get_tx_expiration_time(&self) -> u64 {
+            self.tx_expiration_time
+        }
+
+        #[ink(message)]
+        pub fn get_transfer_nonce(&self) -> u128 {
+            self.transfer_nonce
+        }
+
+        #[ink(message)]
+        pub fn is_strict_nonce_ordering(&self) -> bool {
+            self.strict_nonce_ordering
+        }
+
+        #[ink(message)]
+        pub fn is_nonce_executed(&self, source_chain_id: u64, transfer_nonce: u128) -> bool {
+            transfer_nonce <= self.get_highest_contiguous_nonce(source_chain_id) || self.executed_nonces.contains_key(&(source_chain_id, transfer_nonce))
+        }
+
+        //  Highest nonce N of the source chain such as all transfers with nonces 1..=N were executed
+        #[ink(message)]
+        pub fn get_highest_contiguous_nonce(&self, source_chain_id: u64) -> u128 {
+            self.contiguous_nonces.get(&source_chain_id).copied().unwrap_or(0)
+        }
+
+        #[ink(message)]
+        pub fn get_destination_chain_id(&self) -> u64 {
+            self.destination_chain_id
+        }
+
+        #[ink(message)]
+        pub fn get_outbound_transfer(&self, transfer_nonce: u128) -> Option<OutboundTransfer> {
+            self.outbound_transfers.get(&transfer_nonce).cloned()
+        }
+
+        //  Returns outbound transfers with nonces from_nonce..=to_nonce, range is limited by MAX_QUERY_RANGE
+        #[ink(message)]
+        pub fn get_outbound_transfers(&self, from_nonce: u128, to_nonce: u128) -> Vec<(u128, OutboundTransfer)> {
+            assert!(from_nonce <= to_nonce, "Start of the range can't be greater than its end");
+            assert!(to_nonce - from_nonce < MAX_QUERY_RANGE, "Requested range of nonces is too big");
+            let mut transfers: Vec<(u128, OutboundTransfer)> = Vec::new();
+            for transfer_nonce in from_nonce..=to_nonce {
+                if let Some(transfer) = self.outbound_transfers.get(&transfer_nonce) {
+                    transfers.push((transfer_nonce, transfer.clone()));
+                }
+            }
+            transfers
+        }
+
+        #[ink(message)]
+        pub fn get_min_amount_to_transfer(&self) -> u128 {
+            self.min_amount_to_transfer
+        }
+
+        //  Returns minimum and maximum transfer amount of the asset
+        #[ink(message)]
+        pub fn get_transfer_limits(&self, asset: AccountId) -> (u128, u128) {
+            let min_amount: u128 = self.min_transfer_amounts.get(&asset).copied().unwrap_or(self.min_amount_to_transfer);
+            let max_amount: u128 = self.max_transfer_amounts.get(&asset).copied().unwrap_or(u128::MAX);
+            (min_amount, max_amount)
+        }
+
+        #[ink(message)]
+        pub fn get_remote_asset(&self, asset: AccountId, remote_chain_id: u64) -> Option<RemoteAsset> {
+            self.remote_assets.get(&(asset, remote_chain_id)).cloned()
+        }
+
+        #[ink(message)]
+        pub fn get_compliance_manager(&self) -> AccountId {
+            self.compliance_manager
+        }
+
+        #[ink(message)]
+        pub fn is_account_blocked(&self, account: AccountId) -> bool {
+            self.blocked_accounts.contains_key(&account)
+        }
+
+        #[ink(message)]
+        pub fn is_receiver_blocked(&self, receiver: String) -> bool {
+            self.blocked_receivers.contains_key(&self.normalize_receiver(&receiver))
+        }
+
+        #[ink(message)]
+        pub fn get_quarantined_swap(&self, message_hash: Vec<u8>) -> Option<SwapMessage> {
+            self.quarantined_swaps.get(&message_hash).cloned()
+        }
+
+        #[ink(message)]
+        pub fn get_quarantined_swaps(&self) -> Vec<Vec<u8>> {
+            let mut swaps: Vec<Vec<u8>> = Vec::new();
+            for el in self.quarantined_swaps.keys() {
+                swaps.push(el.clone());
+            }
+            swaps
+        }
+
+        #[ink(message)]
+        pub fn is_freeze_conflicts(&self) -> bool {
+            self.freeze_conflicts
+        }
+
+        #[ink(message)]
+        pub fn is_nonce_frozen(&self, source_chain_id: u64, transfer_nonce: u128) -> bool {
+            self.frozen_nonces.contains_key(&(source_chain_id, transfer_nonce))
+        }
+
+        #[ink(message)]
+        pub fn get_swap_variants(&self, source_chain_id: u64, transfer_nonce: u128) -> Vec<Vec<u8>> {
+            self.swap_variants.get(&(source_chain_id, transfer_nonce)).cloned().unwrap_or(Vec::new())
+        }
+
+        #[ink(message)]
+        pub fn get_tokens(&self) -> Vec<AccountId> {
+            let mut tokens: Vec<AccountId> = Vec::new();
+            for el in self.tokens.keys() {
+                tokens.push(el.clone());
+            }
+            tokens
+        }
+
+        #[ink(message)]
+        pub fn get_validators(&self) -> Vec<AccountId> {
+            let mut validators: Vec<AccountId> = Vec::new();
+            for el in self.validators.keys() {
+                validators.push(el.clone());
+            }
+            validators
+        }
+
+        #[ink(message)]
+        pub fn get_chain_id(&self) -> u8 {
+            self.chain_id
+        }
+
+        #[ink(message)]
+        pub fn get_request_swaps(&self) -> Vec<Vec<u8>> {
+            let mut swaps: Vec<Vec<u8>> = Vec::new();
+            for el in self.swap_requests.keys() {
+                swaps.push(el.clone());
+            }
+            swaps
+        }
+
+        #[ink(message)]
+        pub fn get_count_of_approvals(&self, message_hash: Vec<u8>) -> u16 {
+            let validators_who_approved_swap: Option<Vec<AccountId>> = self.get_validators_who_approved(&message_hash);
+            match validators_who_approved_swap {
+                Some(n) => {
+                    return n.len() as u16;
+                },
+                None => {
+                    return 0;
+                }
+            }
+        }
+
+        #[ink(message)]
+        pub fn get_count_of_root_approvals(&self, root: Vec<u8>) -> u16 {
+            match self.root_requests.get(&root) {
+                Some(n) => n.len() as u16,
+                None => 0,
+            }
+        }
+
+        #[ink(message)]
+        pub fn is_root_approved(&self, root: Vec<u8>) -> bool {
+            self.approved_roots.contains_key(&root)
+        }
+
+        #[ink(message)]
+        pub fn is_swap_executed(&self, message_hash: Vec<u8>) -> bool {
+            self.executed_swaps.contains_key(&message_hash)
+        }
+
+        #[ink(message)]
+        pub fn get_outbound_root(&self) -> Vec<u8> {
+            let mut node: Vec<u8> = ZERO_ADDRESS_BYTES.to_vec();
+            let mut zero_hash: Vec<u8> = ZERO_ADDRESS_BYTES.to_vec();
+            let mut size: u64 = self.outbound_leaf_count;
+            for height in 0..OUTBOUND_TREE_DEPTH {
+                if size & 1 == 1 {
+                    node = self.keccak_pair(&self.outbound_branch[height], &node);
+                } else {
+                    node = self.keccak_pair(&node, &zero_hash);
+                }
+                zero_hash = self.keccak_pair(&zero_hash, &zero_hash);
+                size = size / 2;
+            }
+            node
+        }
+
+        #[ink(message)]
+        pub fn get_outbound_leaf_count(&self) -> u64 {
+            self.outbound_leaf_count
+        }
+
+        #[ink(message)]
+        pub fn compute_outbound_leaf(&self, receiver: String, sender: AccountId, amount: u128, asset: AccountId, transfer_nonce: u128, timestamp: u64, remote_asset: Vec<u8>, remote_amount: u128) -> Vec<u8> {
+            let mut encoded: Vec<u8> = Vec::new();
+            encoded.push(self.chain_id);
+            encoded.extend_from_slice(receiver.as_bytes());
+            encoded.extend_from_slice(sender.encode().as_slice());
+            encoded.extend_from_slice(&self.to_uint256(amount));
+            encoded.extend_from_slice(asset.encode().as_slice());
+            encoded.extend_from_slice(&self.to_uint256(transfer_nonce));
+            encoded.extend_from_slice(&timestamp.to_be_bytes());
+            encoded.extend_from_slice(remote_asset.as_slice());
+            encoded.extend_from_slice(&self.to_uint256(remote_amount));
+            let mut hasher = Keccak256::new();
+            hasher.input(encoded.as_slice());
+            hasher.result().to_vec()
+        }
+
+        #[ink(message)]
+        pub fn get_approved_weight(&self, message_hash: Vec<u8>) -> u64 {
+            match self.get_validators_who_approved(&message_hash) {
+                Some(n) => self.get_approvers_weight(&n),
+                None => 0,
+            }
+        }
+
+        #[ink(message)]
+        pub fn get_weight_threshold(&self) -> u64 {
+            self.weight_threshold
+        }
+
+        #[ink(message)]
+        pub fn is_weighted_rewards(&self) -> bool {
+            self.weighted_rewards
+        }
+
+        #[ink(message)]
+        pub fn get_validator_rewards(&self, validator: AccountId) -> u128 {
+            let rewards_amount: Option<&u128> = self.validator_rewards.get(&validator);
+
+            match rewards_amount {
+                Some(rew) => return *rew,
+                None => return 0,
+            }
+        }
+
+        //  This method necessary such as there can be not relevant and did not approved requests which will take storage
+        //  and to clean the storage and save some money owner can use this method
+        #[ink(message)]
+        pub fn clean_request_swaps_list(&mut self) {
+            self.ensure_owner(self.env().caller());
+            self.clear_swap_requests();
+        }
+
+        //  When it's on, swaps of the source transfer with conflicting messages can't be executed
+        //  until owner resolves the conflict
+        #[ink(message)]
+        pub fn set_freeze_conflicts(&mut self, is_frozen: bool) {
+            self.ensure_owner(self.env().caller());
+            self.freeze_conflicts = is_frozen;
+        }
+
+        //  Drops all variants of the swap except the accepted one, or all of them if nothing is accepted.
+        //  Accepted swap which reached quorum while it was frozen is executed right away.
+        #[ink(message)]
+        pub fn resolve_conflict(&mut self, source_chain_id: u64, transfer_nonce: u128, accepted_hash: Option<Vec<u8>>) {
+            self.ensure_owner(self.env().caller());
+            let key: (u64, u128) = (source_chain_id, transfer_nonce);
+            let variants: Vec<Vec<u8>> = self.get_swap_variants(source_chain_id, transfer_nonce);
+            assert!(!variants.is_empty(), "There are no pending swaps for this transfer");
+            if let Some(hash) = accepted_hash.as_ref() {
+                assert!(self.is_in_hashes(&variants, hash), "Accepted hash isn't a variant of this transfer");
+            }
+
+            self.swap_variants.take(&key);
+            for variant in variants.iter() {
+                if Some(variant) != accepted_hash.as_ref() {
+                    self.swap_requests.take(variant);
+                    self.frozen_swaps.take(variant);
+                }
+            }
+            self.frozen_nonces.take(&key);
+
+            if let Some(hash) = accepted_hash.clone() {
+                let mut accepted_variants: Vec<Vec<u8>> = Vec::new();
+                accepted_variants.push(hash.clone());
+                self.swap_variants.insert(key, accepted_variants);
+
+                if let Some(transfer_info) = self.frozen_swaps.take(&hash) {
+                    let validators_who_approved_swap: Vec<AccountId> = self.get_validators_who_approved(&hash).unwrap_or(Vec::new());
+                    if self.has_quorum(&validators_who_approved_swap) && !self.public_relaying {
+                        self.execute_approved_swap(transfer_info, hash, self.env().caller());
+                    }
+                }
+            }
+
+            self.env().emit_event(ConflictResolved {
+                source_chain_id,
+                transfer_nonce,
+                accepted_hash,
+            });
+        }
+
+        //  Approvals and batch roots collected with the previous scheme can't be matched anymore, so they are dropped
+        #[ink(message)]
+        pub fn set_hash_scheme(&mut self, new_hash_scheme: HashScheme) {
+            self.ensure_owner(self.env().caller());
+            self.hash_scheme = new_hash_scheme;
+            self.clear_swap_requests();
+            self.clear_root_requests();
+        }
+
+        #[ink(message)]
+        pub fn get_hash_scheme(&self) -> HashScheme {
+            self.hash_scheme
+        }
+
+        //  Swap hashes and batch roots depend on the domain, so approvals collected before are dropped
+        #[ink(message)]
+        pub fn set_domain_chain_id(&mut self, new_domain_chain_id: u64) {
+            self.ensure_owner(self.env().caller());
+            self.domain_chain_id = new_domain_chain_id;
+            self.clear_swap_requests();
+            self.clear_root_requests();
+        }
+
+        #[ink(message)]
+        pub fn get_domain_chain_id(&self) -> u64 {
+            self.domain_chain_id
+        }
+
+        #[ink(message)]
+        pub fn get_protocol_version(&self) -> u16 {
+            PROTOCOL_VERSION
+        }
+
+        #[ink(message)]
+        pub fn get_domain_separator(&self) -> Vec<u8> {
+            self.domain_separator()
+        }
+
+        #[ink(message)]
+        pub fn compute_swap_hash(&self, transfer_info: SwapMessage) -> Vec<u8> {
+            self.hash_message(transfer_info)
+        }
+
+        #[ink(message)]
+        pub fn set_upgrade_delay(&mut self, new_upgrade_delay: u64) {
+            self.ensure_owner(self.env().caller());
+            assert!(new_upgrade_delay >= ONE_DAY, "Upgrade delay can't be less than one day");
+            assert!(self.pending_upgrade.is_none(), "Upgrade delay can't be changed while upgrade is scheduled");
+            self.upgrade_delay = new_upgrade_delay;
+        }
+
+        #[ink(message)]
+        pub fn schedule_upgrade(&mut self, successor: AccountId) {
+            self.ensure_owner(self.env().caller());
+            self.ensure_not_upgraded();
+            assert!(successor != self.env().account_id(), "Bridge can't be upgraded to itself");
+            let execute_after: u64 = self.env().block_timestamp() / 1000 + self.upgrade_delay;
+            self.pending_upgrade = Some(PendingUpgrade {
+                successor,
+                execute_after,
+            });
+            self.env().emit_event(UpgradeScheduled {
+                successor,
+                execute_after,
+            });
+        }
+
+        #[ink(message)]
+        pub fn cancel_upgrade(&mut self) {
+            self.ensure_owner(self.env().caller());
+            let pending_upgrade: Option<PendingUpgrade> = self.pending_upgrade.take();
+            assert!(pending_upgrade.is_some(), "There is no scheduled upgrade");
+            self.env().emit_event(UpgradeCancelled {
+                successor: pending_upgrade.unwrap().successor,
+            });
+        }
+
+        //  Moves all locked coins to the successor contract and stops accepting transfers and swaps.
+        //  The successor has to import the state returned by `export_state` through its `migrate` method.
+        #[ink(message)]
+        pub fn execute_upgrade(&mut self) {
+            self.ensure_owner(self.env().caller());
+            self.ensure_not_upgraded();
+            let pending_upgrade: PendingUpgrade = self.pending_upgrade.clone().expect("There is no scheduled upgrade");
+            let current_time: u64 = self.env().block_timestamp() / 1000;
+            assert!(current_time >= pending_upgrade.execute_after, "Upgrade timelock hasn't expired yet");
+
+            // Existential deposit stays with the contract, otherwise the transfer would reap it and fail
+            let moved_balance: u128 = self.env().balance().saturating_sub(self.env().minimum_balance());
+            assert!(self.env().transfer(pending_upgrade.successor, moved_balance).is_ok(), "Error while transfer coins to the successor");
+
+            self.pending_upgrade = None;
+            self.successor = Some(pending_upgrade.successor);
+            self.env().emit_event(Upgraded {
+                successor: pending_upgrade.successor,
+                moved_balance,
+                transfer_nonce: self.transfer_nonce,
+            });
+        }
+
+        #[ink(message)]
+        pub fn migrate(&mut self, state: BridgeState) {
+            self.ensure_owner(self.env().caller());
+            assert!(state.storage_version <= STORAGE_VERSION, "Migrated state has newer storage version than the contract supports");
+            assert!(self.transfer_nonce == 0 && self.swap_requests.len() == 0, "Bridge is already in use and can't be migrated");
+            assert!(state.chain_id == self.chain_id, "Migrated state's chain ID doesn't match contract's chain ID");
+            assert!(!self.migrated, "Bridge was already migrated");
+
+            // Unversioned layout doesn't differ from the first version except the upgrade settings,
+            // which are kept as they were set by constructor
+            let current_timestamp: u64 = self.env().block_timestamp() / 1000;
+            self.validators = StorageHashMap::default();
+            for validator in state.validators.iter() {
+                let validator_info: ValidatorInfo = self.new_validator_info();
+                self.validators.insert(*validator, validator_info);
+            }
+            for token in state.tokens.iter() {
+                self.tokens.insert(*token, true);
+            }
+            for (asset, limit) in state.daily_limits.iter() {
+                self.daily_limit.insert(*asset, *limit);
+                self.daily_limit_set_time.insert(*asset, current_timestamp);
+                self.daily_spend.insert(*asset, 0);
+            }
+            for (validator, rewards) in state.validator_rewards.iter() {
+                self.validator_rewards.insert(*validator, *rewards);
+                self.accrued_rewards = self.accrued_rewards + *rewards;
+            }
+            self.default_fee.fee_bps = (state.fee * 100) as u16;
+            self.signature_threshold = state.signature_threshold;
+            self.max_validator_count = state.max_validator_count;
+            self.tx_expiration_time = state.tx_expiration_time;
+            self.transfer_nonce = state.transfer_nonce;
+            self.min_amount_to_transfer = state.min_amount_to_transfer;
+
+            // Since the second version inbound nonces are moved, so transfers executed by the old bridge can't be replayed
+            if state.storage_version >= 2 {
+                for (source_chain_id, transfer_nonce) in state.executed_nonces.iter() {
+                    self.executed_nonces.insert((*source_chain_id, *transfer_nonce), true);
+                }
+                for (source_chain_id, transfer_nonce) in state.contiguous_nonces.iter() {
+                    self.contiguous_nonces.insert(*source_chain_id, *transfer_nonce);
+                }
+            }
+
+            // Since the third version validators are moved with their records instead of being added anew
+            if state.storage_version >= 3 {
+                for (validator, validator_info) in state.validator_infos.iter() {
+                    self.validators.insert(*validator, validator_info.clone());
+                }
+            }
+
+            // Since the fourth version blocklists and quarantined swaps are moved, so they stay blocked after upgrade
+            if state.storage_version >= 4 {
+                for account in state.blocked_accounts.iter() {
+                    self.blocked_accounts.insert(*account, true);
+                }
+                for receiver in state.blocked_receivers.iter() {
+                    self.blocked_receivers.insert(self.normalize_receiver(receiver), true);
+                }
+                for (message_hash, transfer_info) in state.quarantined_swaps.iter() {
+                    self.quarantined_swaps.insert(message_hash.clone(), transfer_info.clone());
+                }
+            }
+
+            // Since the fifth version treasury fees and the liquidity pool are moved with their owners' shares
+            if state.storage_version >= 5 {
+                for (asset, amount) in state.treasury_balances.iter() {
+                    self.treasury_balances.insert(*asset, *amount);
+                }
+                for (provider, shares) in state.lp_shares.iter() {
+                    self.lp_shares.insert(*provider, *shares);
+                }
+                self.total_lp_shares = state.total_lp_shares;
+                self.pool_liquidity = state.pool_liquidity;
+                self.pool_borrowed = state.pool_borrowed;
+            }
+
+            // Since the sixth version fees are moved in basis points with their bounds and per-asset schedule
+            if state.storage_version >= 6 {
+                self.default_fee = state.default_fee;
+                for (key, fee_config) in state.fee_schedule.iter() {
+                    self.fee_schedule.insert(*key, *fee_config);
+                }
+            }
+
+            // Coins moved by the old bridge which don't belong to validators, treasury or liquidity providers
+            // back transfers which weren't released yet
+            self.locked_liquidity = self.env().balance().saturating_sub(self.native_obligations());
+
+            self.storage_version = STORAGE_VERSION;
+            self.migrated = true;
+        }
+
+        #[ink(message)]
+        pub fn export_state(&self) -> BridgeState {
+            let mut daily_limits: Vec<(AccountId, u128)> = Vec::new();
+            for (asset, limit) in self.daily_limit.iter() {
+                daily_limits.push((asset.clone(), limit.clone()));
+            }
+            let mut validator_rewards: Vec<(AccountId, u128)> = Vec::new();
+            for (validator, rewards) in self.validator_rewards.iter() {
+                validator_rewards.push((validator.clone(), rewards.clone()));
+            }
+            let mut executed_nonces: Vec<(u64, u128)> = Vec::new();
+            for (source_chain_id, transfer_nonce) in self.executed_nonces.keys() {
+                executed_nonces.push((*source_chain_id, *transfer_nonce));
+            }
+            let mut contiguous_nonces: Vec<(u64, u128)> = Vec::new();
+            for (source_chain_id, transfer_nonce) in self.contiguous_nonces.iter() {
+                contiguous_nonces.push((*source_chain_id, *transfer_nonce));
+            }
+            let mut validator_infos: Vec<(AccountId, ValidatorInfo)> = Vec::new();
+            for (validator, validator_info) in self.validators.iter() {
+                validator_infos.push((validator.clone(), validator_info.clone()));
+            }
+            let mut blocked_accounts: Vec<AccountId> = Vec::new();
+            for account in self.blocked_accounts.keys() {
+                blocked_accounts.push(account.clone());
+            }
+            let mut blocked_receivers: Vec<String> = Vec::new();
+            for receiver in self.blocked_receivers.keys() {
+                blocked_receivers.push(receiver.clone());
+            }
+            let mut quarantined_swaps: Vec<(Vec<u8>, SwapMessage)> = Vec::new();
+            for (message_hash, transfer_info) in self.quarantined_swaps.iter() {
+                quarantined_swaps.push((message_hash.clone(), transfer_info.clone()));
+            }
+            let mut treasury_balances: Vec<(AccountId, u128)> = Vec::new();
+            for (asset, amount) in self.treasury_balances.iter() {
+                treasury_balances.push((asset.clone(), amount.clone()));
+            }
+            let mut lp_shares: Vec<(AccountId, u128)> = Vec::new();
+            for (provider, shares) in self.lp_shares.iter() {
+                lp_shares.push((provider.clone(), shares.clone()));
+            }
+            let mut fee_schedule: Vec<((AccountId, TransferDirection), FeeConfig)> = Vec::new();
+            for (key, fee_config) in self.fee_schedule.iter() {
+                fee_schedule.push((key.clone(), fee_config.clone()));
+            }
+            BridgeState {
+                storage_version: self.storage_version,
+                validators: self.get_validators(),
+                tokens: self.get_tokens(),
+                daily_limits,
+                validator_rewards,
+                fee: self.get_fee(),
+                signature_threshold: self.signature_threshold,
+                max_validator_count: self.max_validator_count,
+                tx_expiration_time: self.tx_expiration_time,
+                transfer_nonce: self.transfer_nonce,
+                chain_id: self.chain_id,
+                min_amount_to_transfer: self.min_amount_to_transfer,
+                executed_nonces,
+                contiguous_nonces,
+                validator_infos,
+                blocked_accounts,
+                blocked_receivers,
+                quarantined_swaps,
+                treasury_balances,
+                lp_shares,
+                total_lp_shares: self.total_lp_shares,
+                pool_liquidity: self.pool_liquidity,
+                pool_borrowed: self.pool_borrowed,
+                default_fee: self.default_fee,
+                fee_schedule,
+            }
+        }
+
+        #[ink(message)]
+        pub fn get_storage_version(&self) -> u16 {
+            self.storage_version
+        }
+
+        #[ink(message)]
+        pub fn get_upgrade_delay(&self) -> u64 {
+            self.upgrade_delay
+        }
+
+        #[ink(message)]
+        pub fn get_pending_upgrade(&self) -> Option<PendingUpgrade> {
+            self.pending_upgrade.clone()
+        }
+
+        #[ink(message)]
+        pub fn get_successor(&self) -> Option<AccountId> {
+            self.successor
+        }
+
+        #[ink(message)]
+        pub fn request_rewards(&mut self) {
+            let caller: AccountId = self.env().caller();
+            let rewards_amount: Option<u128> = self.validator_rewards.take(&caller);
+            assert!(rewards_amount.is_some(), "This address doesn't have any rewards");
+
+            self.accrued_rewards = self.accrued_rewards - rewards_amount.unwrap();
+            self.ensure_native_payout_covered(rewards_amount.unwrap());
+            assert!(self.env().transfer(caller, rewards_amount.unwrap()).is_ok(), "Error while transfer rewards to the validator");
+        }
+
+        // Liquidity provider method
+        //  Pool coins back releases of inbound swaps when locked liquidity isn't enough,
+        //  borrowed coins are returned to the pool by the next outbound transfers
+        #[ink(message, payable)]
+        pub fn provide_liquidity(&mut self) -> u128 {
+            self.ensure_not_upgraded();
+            let provider: AccountId = self.env().caller();
+            let amount: u128 = self.env().transferred_balance();
+            assert!(amount > 0, "Deposit must be more than zero");
+
+            let shares: u128 = if self.total_lp_shares == 0 || self.pool_liquidity == 0 {
+                amount
+            } else {
+                amount.checked_mul(self.total_lp_shares).expect("Liquidity shares calculation overflows") / self.pool_liquidity
+            };
+            assert!(shares > 0, "Deposit is too small");
+
+            let existing_shares: u128 = self.get_lp_shares(provider);
+            self.lp_shares.insert(provider, existing_shares + shares);
+            self.total_lp_shares = self.total_lp_shares + shares;
+            self.pool_liquidity = self.pool_liquidity + amount;
+
+            self.env().emit_event(LiquidityProvided {
+                provider,
+                amount,
+                shares,
+            });
+            shares
+        }
+
+        // Liquidity provider method
+        #[ink(message)]
+        pub fn withdraw_liquidity(&mut self, shares: u128) -> u128 {
+            let provider: AccountId = self.env().caller();
+            let existing_shares: u128 = self.get_lp_shares(provider);
+            assert!(shares > 0 && shares <= existing_shares, "Not enough shares to withdraw");
+
+            let amount: u128 = shares.checked_mul(self.pool_liquidity).expect("Liquidity amount calculation overflows") / self.total_lp_shares;
+            assert!(amount <= self.pool_liquidity - self.pool_borrowed, "Not enough available liquidity in the pool");
+
+            if existing_shares == shares {
+                self.lp_shares.take(&provider);
+            } else {
+                self.lp_shares.insert(provider, existing_shares - shares);
+            }
+            self.total_lp_shares = self.total_lp_shares - shares;
+            self.pool_liquidity = self.pool_liquidity - amount;
+
+            self.ensure_native_payout_covered(amount);
+            assert!(self.env().transfer(provider, amount).is_ok(), "Error while transfer coins to the liquidity provider");
+
+            self.env().emit_event(LiquidityWithdrawn {
+                provider,
+                amount,
+                shares,
+            });
+            amount
+        }
+
+        #[ink(message)]
+        pub fn get_lp_shares(&self, provider: AccountId) -> u128 {
+            self.lp_shares.get(&provider).copied().unwrap_or(0)
+        }
+
+        #[ink(message)]
+        pub fn get_total_lp_shares(&self) -> u128 {
+            self.total_lp_shares
+        }
+
+        #[ink(message)]
+        pub fn get_lp_fee_share(&self) -> u128 {
+            self.lp_fee_share
+        }
+
+        //  Returns total liquidity of the pool and the part of it which is available for withdrawal
+        #[ink(message)]
+        pub fn get_pool_liquidity(&self) -> (u128, u128) {
+            (self.pool_liquidity, self.pool_liquidity - self.pool_borrowed)
+        }
+
+        #[ink(message)]
+        pub fn get_solvency_report(&self) -> SolvencyReport {
+            let balance: u128 = self.env().balance();
+            let zero_address: AccountId = AccountId::from(ZERO_ADDRESS_BYTES);
+            let treasury_balance: u128 = self.get_treasury_balance(zero_address);
+            let obligations: u128 = self.native_obligations();
+            SolvencyReport {
+                balance,
+                locked_liquidity: self.locked_liquidity,
+                accrued_rewards: self.accrued_rewards,
+                treasury_balance,
+                pool_liquidity: self.pool_liquidity,
+                pool_borrowed: self.pool_borrowed,
+                surplus: balance.saturating_sub(obligations),
+                deficit: obligations.saturating_sub(balance),
+            }
+        }
+
+        #[ink(message)]
+        pub fn withdraw_treasury(&mut self, asset: AccountId) {
+            self.ensure_not_upgraded();
+            let caller: AccountId = self.env().caller();
+            assert!(caller == self.treasury, "Only treasury can withdraw treasury fees");
+            let amount: u128 = self.treasury_balances.take(&asset).unwrap_or(0);
+            assert!(amount > 0, "Treasury doesn't have any fees in this asset");
+
+            let zero_address: AccountId = AccountId::from(ZERO_ADDRESS_BYTES);
+            if asset == zero_address {
+                self.ensure_native_payout_covered(amount);
+                assert!(self.env().transfer(caller, amount).is_ok(), "Error while transfer fees to the treasury");
+            } else {
+                assert!(self.token_contract.mint(amount, caller), "Error while mint fees for the treasury");
+            }
+
+            self.env().emit_event(TreasuryWithdrawal {
+                treasury: caller,
+                asset,
+                amount,
+            });
+        }
+
+        // Validator method
+        #[ink(message)]
+        pub fn request_swap(&mut self, transfer_info: SwapMessage) {
+            self.ensure_not_upgraded();
+            let caller: AccountId = self.env().caller();
+            assert!(self.is_active_validator(&caller), "Only Validator can send requests to swap assets");
+            self.record_validator_approval(&caller);
+
+            assert!(transfer_info.chain_id == self.chain_id, "Swap request's chain ID doesn't match contract's chain ID");
+
+            assert!(self.check_expiration_time(transfer_info.timestamp.clone()), "Transaction can't be sent because of expiration time");
+
+            assert!(self.check_asset(&transfer_info.asset), "Unknown asset is trying to transfer");
+
+            self.check_transfer_amount(&transfer_info.asset, self.to_local_amount(&transfer_info));
+
+            let message_hash: Vec<u8> = self.hash_message(transfer_info.clone());
+
+            assert!(!self.executed_swaps.contains_key(&message_hash), "Swap was already executed");
+            assert!(!self.is_nonce_executed(transfer_info.source_chain_id, transfer_info.transfer_nonce), "Transfer with this nonce was already executed");
+
+            self.index_swap_variant(transfer_info.source_chain_id, transfer_info.transfer_nonce, &message_hash);
+            let is_frozen: bool = self.is_nonce_frozen(transfer_info.source_chain_id, transfer_info.transfer_nonce);
+
+            let validators_who_approved_swap: Option<Vec<AccountId>> = self.get_validators_who_approved(&message_hash);
+            match validators_who_approved_swap {
+                Some(n) => {
+                    assert!(self.is_in(&n, &caller) == false, "This Validator has already sent approval");
+                    let mut updated_validator_list: Vec<AccountId> = n.clone();
+                    updated_validator_list.push(caller);
+                    if self.has_quorum(&updated_validator_list) && !self.public_relaying && !is_frozen {
+                        self.execute_approved_swap(transfer_info, message_hash, caller);
+                    } else {
+                        // Other validators can't approve it again, so the swap is kept until owner resolves the conflict
+                        if is_frozen && self.has_quorum(&updated_validator_list) {
+                            self.frozen_swaps.insert(message_hash.clone(), transfer_info);
+                        }
+                        self.swap_requests.insert(message_hash, updated_validator_list);
+                    }
+                },
+                None => {
+                    let mut init_vec_of_validators: Vec<AccountId> = Vec::new();
+                    init_vec_of_validators.push(caller);
+                    self.swap_requests.insert(message_hash, init_vec_of_validators);
+                }
+            }
+        }
+
+        // Validator method
+        #[ink(message)]
+        pub fn revoke_approval(&mut self, message_hash: Vec<u8>) {
+            let caller: AccountId = self.env().caller();
+            assert!(!self.executed_swaps.contains_key(&message_hash), "Swap was already executed");
+            let validators_who_approved_swap: Vec<AccountId> = self.get_validators_who_approved(&message_hash).expect("There is no pending swap with this hash");
+            assert!(self.is_in(&validators_who_approved_swap, &caller), "This Validator hasn't approved the swap");
+
+            let updated_validator_list: Vec<AccountId> = validators_who_approved_swap.into_iter().filter(|validator| *validator != caller).collect();
+            if updated_validator_list.is_empty() {
+                self.swap_requests.take(&message_hash);
+            } else {
+                self.swap_requests.insert(message_hash.clone(), updated_validator_list);
+            }
+
+            self.env().emit_event(ApprovalRevoked {
+                validator: caller,
+                message_hash,
+            });
+        }
+
+        // Relayer method
+        #[ink(message)]
+        pub fn execute_swap(&mut self, transfer_info: SwapMessage) {
+            self.ensure_not_upgraded();
+            assert!(self.public_relaying, "Public relaying is disabled");
+            assert!(!self.is_nonce_frozen(transfer_info.source_chain_id, transfer_info.transfer_nonce), "Swaps of this transfer are frozen until owner resolves the conflict");
+            let message_hash: Vec<u8> = self.hash_message(transfer_info.clone());
+            let validators_who_approved_swap: Vec<AccountId> = self.get_validators_who_approved(&message_hash).unwrap_or(Vec::new());
+            assert!(self.has_quorum(&validators_who_approved_swap), "Swap doesn't have enough approvals");
+            self.execute_approved_swap(transfer_info, message_hash, self.env().caller());
+        }
+
+        // Validator method
+        //  Approves Merkle root of the batch of inbound transfers. Leaves of the tree are swap hashes
+        //  and pairs are hashed in sorted order with the hasher of the current hash scheme.
+        #[ink(message)]
+        pub fn approve_root(&mut self, root: Vec<u8>) {
+            self.ensure_not_upgraded();
+            let caller: AccountId = self.env().caller();
+            assert!(self.is_active_validator(&caller), "Only Validator can approve batch roots");
+            self.record_validator_approval(&caller);
+            assert!(!self.approved_roots.contains_key(&root), "Root is already approved");
+
+            let mut validators_who_approved_root: Vec<AccountId> = match self.root_requests.get(&root) {
+                Some(n) => n.clone(),
+                None => Vec::new(),
+            };
+            assert!(self.is_in(&validators_who_approved_root, &caller) == false, "This Validator has already sent approval");
+            validators_who_approved_root.push(caller);
+
+            if self.has_quorum(&validators_who_approved_root) {
+                let timestamp: u64 = self.env().block_timestamp() / 1000;
+                self.root_requests.take(&root);
+                self.approved_roots.insert(root.clone(), timestamp);
+                self.env().emit_event(RootApproved {
+                    root,
+                    timestamp,
+                });
+            } else {
+                self.root_requests.insert(root, validators_who_approved_root);
+            }
+        }
+
+        // Receiver or relayer method
+        #[ink(message)]
+        pub fn claim(&mut self, transfer_info: SwapMessage, root: Vec<u8>, proof: Vec<Vec<u8>>) {
+            self.ensure_not_upgraded();
+            assert!(self.approved_roots.contains_key(&root), "Root isn't approved by validators");
+
+            assert!(transfer_info.chain_id == self.chain_id, "Swap request's chain ID doesn't match contract's chain ID");
+
+            assert!(self.check_asset(&transfer_info.asset), "Unknown asset is trying to transfer");
+
+            assert!(!self.is_nonce_frozen(transfer_info.source_chain_id, transfer_info.transfer_nonce), "Swaps of this transfer are frozen until owner resolves the conflict");
+
+            let amount: u128 = self.to_local_amount(&transfer_info);
+            self.check_transfer_amount(&transfer_info.asset, amount);
+
+            let message_hash: Vec<u8> = self.hash_message(transfer_info.clone());
+            assert!(!self.executed_swaps.contains_key(&message_hash), "Swap was already executed");
+            assert!(self.verify_merkle_proof(&message_hash, &proof, &root), "Invalid Merkle proof");
+
+            self.settle_swap(transfer_info.clone(), message_hash.clone(), self.env().caller());
+
+            self.env().emit_event(Claimed {
+                root,
+                message_hash,
+                receiver: transfer_info.receiver,
+                amount,
+                asset: transfer_info.asset,
+                transfer_nonce: transfer_info.transfer_nonce,
+            });
+        }
+
+        // User method
+        #[ink(message, payable)]
+        pub fn transfer_coin(&mut self, receiver: String) -> bool {
+            self.make_coin_transfer(receiver, u128::MAX)
+        }
+
+        // User method
+        //  Fails if the fee at the moment of execution is bigger than max_fee
+        #[ink(message, payable)]
+        pub fn transfer_coin_with_max_fee(&mut self, receiver: String, max_fee: u128) -> bool {
+            self.make_coin_transfer(receiver, max_fee)
+        }
+
+        // User method
+        #[ink(message)]
+        pub fn transfer_token(&mut self, receiver: String, amount: u128, asset: AccountId) -> bool {
+            self.make_token_transfer(receiver, amount, asset, u128::MAX)
+        }
+
+        // User method
+        //  Fails if the fee at the moment of execution is bigger than max_fee
+        #[ink(message)]
+        pub fn transfer_token_with_max_fee(&mut self, receiver: String, amount: u128, asset: AccountId, max_fee: u128) -> bool {
+            self.make_token_transfer(receiver, amount, asset, max_fee)
+        }
+
+        fn make_coin_transfer(&mut self, receiver: String, max_fee: u128) -> bool {
+            self.ensure_not_upgraded();
+            self.ensure_not_blocked(self.env().caller(), &receiver);
+            let attached_deposit: u128 = self.env().transferred_balance();
+
+            let zero_address: AccountId = AccountId::from(ZERO_ADDRESS_BYTES);
+
+            self.check_transfer_amount(&zero_address, attached_deposit);
+
+            self.check_asset_daily_limit(&zero_address, attached_deposit);
+
+            self.increase_transfer_nonce();
+
+            let sender: AccountId = self.env().caller();
+
+            let mut fee_amount: u128 = self.compute_fee(zero_address, TransferDirection::Outbound, attached_deposit, sender);
+            let (remote_asset, remote_amount, dust) = self.to_remote_amount(&zero_address, attached_deposit - fee_amount);
+            fee_amount = fee_amount + dust;
+            assert!(fee_amount <= max_fee, "Fee is bigger than maximum fee set by the sender");
+            self.collect_fee(zero_address, fee_amount, None);
+            let net_amount: u128 = attached_deposit - fee_amount;
+            let repaid_to_pool: u128 = if net_amount < self.pool_borrowed { net_amount } else { self.pool_borrowed };
+            self.pool_borrowed = self.pool_borrowed - repaid_to_pool;
+            self.locked_liquidity = self.locked_liquidity + net_amount - repaid_to_pool;
+
+            let timestamp: u64 = self.env().block_timestamp() / 1000;
+            self.record_outbound_transfer(receiver.clone(), sender, net_amount, zero_address, timestamp, remote_asset.clone(), remote_amount);
+
+            self.env().emit_event(Transfer {
+                receiver,
+                sender,
+                amount: attached_deposit,
+                fee: fee_amount,
+                net_amount,
+                asset: zero_address,
+                transfer_nonce: self.transfer_nonce,
+                timestamp,
+                remote_asset,
+                remote_amount,
+            });
+            true
+        }
+
+        fn make_token_transfer(&mut self, receiver: String, amount: u128, asset: AccountId, max_fee: u128) -> bool {
+            self.ensure_not_upgraded();
+            assert!(self.check_asset(&asset), "Unknown asset is trying to transfer");
+            self.check_transfer_amount(&asset, amount);
+            self.check_asset_daily_limit(&asset, amount);
+            let caller: AccountId = self.env().caller();
+            self.ensure_not_blocked(caller, &receiver);
+            assert!(self.token_contract.balance_of(caller) >= amount, "Sender doesn't have enough tokens to make transfer");
+            assert!(self.token_contract.burn(amount.clone(), caller), "Error while burn sender's tokens");
+            self.increase_transfer_nonce();
+
+            let mut fee_amount: u128 = self.compute_fee(asset, TransferDirection::Outbound, amount, caller);
+            let (remote_asset, remote_amount, dust) = self.to_remote_amount(&asset, amount - fee_amount);
+            fee_amount = fee_amount + dust;
+            assert!(fee_amount <= max_fee, "Fee is bigger than maximum fee set by the sender");
+            self.collect_fee(asset, fee_amount, None);
+            let net_amount: u128 = amount - fee_amount;
+
+            let timestamp: u64 = self.env().block_timestamp() / 1000;
+            self.record_outbound_transfer(receiver.clone(), caller, net_amount, asset, timestamp, remote_asset.clone(), remote_amount);
+
+            self.env().emit_event(Transfer {
+                receiver,
+                sender: caller,
+                amount,
+                fee: fee_amount,
+                net_amount,
+                asset,
+                transfer_nonce: self.transfer_nonce,
+                timestamp,
+                remote_asset,
+                remote_amount,
+            });
+            true
+        }
+
+        //  Splits fee of the transfer between treasury and validators.
+        //  Validators are rewarded only in native coins, token fees go to treasury or aren't minted at all.
+        fn collect_fee(&mut self, asset: AccountId, collected_fee: u128, relayer: Option<AccountId>) {
+            let zero_address: AccountId = AccountId::from(ZERO_ADDRESS_BYTES);
+            let mut fee_amount: u128 = collected_fee;
+
+            if let Some(relayer_address) = relayer {
+                let relayer_amount: u128 = (fee_amount * self.relayer_bonus) / 100;
+                if relayer_amount > 0 {
+                    if asset == zero_address {
+                        let existing_rewards: u128 = self.get_validator_rewards(relayer_address);
+                        self.validator_rewards.insert(relayer_address, existing_rewards + relayer_amount);
+                        self.accrued_rewards = self.accrued_rewards + relayer_amount;
+                    } else {
+                        assert!(self.token_contract.mint(relayer_amount, relayer_address), "Error while mint bonus for the relayer");
+                    }
+                    fee_amount = fee_amount - relayer_amount;
+                }
+            }
+
+            if asset == zero_address {
+                let lp_amount: u128 = (fee_amount * self.lp_fee_share) / 100;
+                if lp_amount > 0 && self.total_lp_shares > 0 {
+                    self.pool_liquidity = self.pool_liquidity + lp_amount;
+                    fee_amount = fee_amount - lp_amount;
+                }
+            }
+
+            let treasury_amount: u128 = (fee_amount * self.treasury_share) / 100;
+
+            if treasury_amount > 0 {
+                let treasury_balance: u128 = self.treasury_balances.get(&asset).copied().unwrap_or(0);
+                self.treasury_balances.insert(asset, treasury_balance + treasury_amount);
+            }
+
+            if asset == zero_address {
+                self.distribute_rewards_for_validators(fee_amount - treasury_amount);
+            }
+        }
+
+        //  Fee never exceeds the transferred amount, payer's fee tier is applied after min and max bounds
+        fn compute_fee(&self, asset: AccountId, direction: TransferDirection, amount: u128, payer: AccountId) -> u128 {
+            let is_charged: bool = match (self.fee_side, direction) {
+                (FeeSide::Both, _) => true,
+                (FeeSide::Source, TransferDirection::Outbound) => true,
+                (FeeSide::Destination, TransferDirection::Inbound) => true,
+                _ => false,
+            };
+            if !is_charged {
+                return 0;
+            }
+            let fee_config: FeeConfig = self.get_asset_fee(asset, direction);
+            let mut fee_amount: u128 = amount / BASIS_POINTS * fee_config.fee_bps as u128
+                + (amount % BASIS_POINTS) * fee_config.fee_bps as u128 / BASIS_POINTS;
+            if fee_amount < fee_config.min_fee {
+                fee_amount = fee_config.min_fee;
+            }
+            if fee_amount > fee_config.max_fee {
+                fee_amount = fee_config.max_fee;
+            }
+            if fee_amount > amount {
+                fee_amount = amount;
+            }
+            match self.fee_tiers.get(&payer) {
+                Some(FeeTier::Exempt) => 0,
+                Some(FeeTier::Discount(discount_bps)) => fee_amount - fee_amount * (*discount_bps as u128) / BASIS_POINTS,
+                None => fee_amount,
+            }
+        }
+
+        fn validate_fee_config(&self, fee_bps: u16, min_fee: u128, max_fee: u128) -> FeeConfig {
+            assert!((fee_bps as u128) < BASIS_POINTS, "Fee should be less than 10000 basis points");
+            assert!(min_fee <= max_fee, "Minimum fee can't be greater than maximum fee");
+            FeeConfig {
+                fee_bps,
+                min_fee,
+                max_fee,
+            }
+        }
+
+        fn distribute_rewards_for_validators(&mut self, rewards_amount: u128) {
+            let active_validators_count: u128 = self.get_active_validators_count() as u128;
+            if active_validators_count == 0 {
+                return;
+            }
+            let mut active_validators_weight: u128 = 0;
+            for validator_info in self.validators.values() {
+                if validator_info.status == ValidatorStatus::Active && self.is_live(validator_info) {
+                    active_validators_weight = active_validators_weight + validator_info.weight as u128;
+                }
+            }
+
+            let mut distributed_rewards: u128 = 0;
+            for (validator_address, validator_info) in self.validators.iter() {
+                if validator_info.status == ValidatorStatus::Active && self.is_live(validator_info) {
+                    let reward: u128 = if self.weighted_rewards {
+                        rewards_amount * validator_info.weight as u128 / active_validators_weight
+                    } else {
+                        rewards_amount / active_validators_count
+                    };
+                    let existing_rewards = self.validator_rewards.get(validator_address);
+                    match existing_rewards {
+                        Some(rew) => {
+                            let updated_reward: u128 = rew + reward;
+                            self.validator_rewards.insert(validator_address.clone(), updated_reward);
+                        },
+                        None => {
+                            self.validator_rewards.insert(validator_address.clone(), reward);
+                        }
+                    }
+                    distributed_rewards = distributed_rewards + reward;
+                }
+            }
+            self.accrued_rewards = self.accrued_rewards + distributed_rewards;
+        }
+
+        fn get_approvers_weight(&self, approvers: &Vec<AccountId>) -> u64 {
+            let mut weight: u64 = 0;
+            for approver in approvers.iter() {
+                if self.is_active_validator(approver) {
+                    weight = weight + self.validators.get(approver).unwrap().weight as u64;
+                }
+            }
+            weight
+        }
+
+        fn get_active_weight(&self, excluded_validator: Option<&AccountId>) -> u64 {
+            let mut weight: u64 = 0;
+            for (validator, validator_info) in self.validators.iter() {
+                if Some(validator) != excluded_validator && validator_info.status == ValidatorStatus::Active {
+                    weight = weight + validator_info.weight as u64;
+                }
+            }
+            weight
+        }
+
+        //  Weighted quorum has to stay reachable by validators which remain active
+        fn ensure_weight_threshold_reachable(&self, excluded_validator: Option<&AccountId>) {
+            assert!(self.get_active_weight(excluded_validator) >= self.weight_threshold, "Weight of active validators can't be less than weight threshold");
+        }
+
+        fn get_active_validators_count(&self) -> u16 {
+            let mut count: u16 = 0;
+            for validator_info in self.validators.values() {
+                if validator_info.status == ValidatorStatus::Active && self.is_live(validator_info) {
+                    count = count + 1;
+                }
+            }
+            count
+        }
+
+        //  Approvals of validators which were removed or suspended aren't counted
+        fn has_quorum(&self, approvers: &Vec<AccountId>) -> bool {
+            if self.weight_threshold > 0 {
+                self.get_approvers_weight(approvers) >= self.weight_threshold
+            } else {
+                self.get_active_approvers_count(approvers) >= self.get_signature_threshold()
+            }
+        }
+
+        fn get_active_approvers_count(&self, approvers: &Vec<AccountId>) -> u16 {
+            let mut count: u16 = 0;
+            for approver in approvers.iter() {
+                if self.is_active_validator(approver) {
+                    count = count + 1;
+                }
+            }
+            count
+        }
+
+        fn is_active_validator(&self, validator: &AccountId) -> bool {
+            match self.validators.get(validator) {
+                Some(validator_info) => validator_info.status == ValidatorStatus::Active,
+                None => false,
+            }
+        }
+
+        fn is_live(&self, validator_info: &ValidatorInfo) -> bool {
+            let current_time: u64 = self.env().block_timestamp() / 1000;
+            self.liveness_period == 0 || current_time - validator_info.last_seen <= self.liveness_period
+        }
+
+        fn record_validator_approval(&mut self, validator: &AccountId) {
+            let current_time: u64 = self.env().block_timestamp() / 1000;
+            let validator_info: &mut ValidatorInfo = self.validators.get_mut(validator).unwrap();
+            validator_info.approvals_count = validator_info.approvals_count + 1;
+            validator_info.last_seen = current_time;
+        }
+
+        //  Suspended and removed validators can't approve, so the rest of active validators must still reach the threshold
+        fn ensure_signature_threshold_reachable(&self, excluded_validator: &AccountId) {
+            let mut count: u16 = 0;
+            for (validator, validator_info) in self.validators.iter() {
+                if validator != excluded_validator && validator_info.status == ValidatorStatus::Active {
+                    count = count + 1;
+                }
+            }
+            assert!(count >= self.signature_threshold, "Count of Validators can't be less than necessary threshold of approvals");
+        }
+
+        //  Validators which aren't active can't approve, so the weight threshold is checked for every other status
+        fn set_validator_status(&mut self, validator: AccountId, status: ValidatorStatus) {
+            if status != ValidatorStatus::Active {
+                self.ensure_weight_threshold_reachable(Some(&validator));
+            }
+            let validator_info: &mut ValidatorInfo = self.validators.get_mut(&validator).expect("Unknown validator");
+            validator_info.status = status;
+            self.env().emit_event(ValidatorStatusChanged {
+                validator,
+                status,
+            });
+        }
+
+        fn new_validator_info(&self) -> ValidatorInfo {
+            ValidatorInfo {
+                name: String::new(),
+                added_at: self.env().block_timestamp() / 1000,
+                status: ValidatorStatus::Active,
+                weight: 1,
+                approvals_count: 0,
+                last_seen: self.env().block_timestamp() / 1000,
+            }
+        }
+
+        //  Converts amount of the outbound transfer to decimals of the asset on the destination chain.
+        //  Amount is rounded down to the precision of the remote asset, the rest is returned as dust which is kept as fee.
+        //  Returns empty identifier and the same amount for assets which aren't registered for the chain.
+        fn to_remote_amount(&self, asset: &AccountId, amount: u128) -> (Vec<u8>, u128, u128) {
+            match self.remote_assets.get(&(*asset, self.destination_chain_id)) {
+                Some(remote) => {
+                    let remote_amount: u128 = self.scale_amount(amount, remote.local_decimals, remote.remote_decimals);
+                    assert!(remote_amount > 0, "Transfer amount is too small for the destination asset");
+                    let dust: u128 = amount - self.scale_amount(remote_amount, remote.remote_decimals, remote.local_decimals);
+                    (remote.remote_asset.clone(), remote_amount, dust)
+                },
+                None => (Vec::new(), amount, 0),
+            }
+        }
+
+        fn to_local_amount(&self, transfer_info: &SwapMessage) -> u128 {
+            match self.remote_assets.get(&(transfer_info.asset, transfer_info.source_chain_id)) {
+                Some(remote) => self.scale_amount(transfer_info.amount, remote.remote_decimals, remote.local_decimals),
+                None => transfer_info.amount,
+            }
+        }
+
+        fn scale_amount(&self, amount: u128, from_decimals: u8, to_decimals: u8) -> u128 {
+            if from_decimals > to_decimals {
+                amount / 10u128.pow((from_decimals - to_decimals) as u32)
+            } else {
+                amount.checked_mul(10u128.pow((to_decimals - from_decimals) as u32)).expect("Scaled amount overflows")
+            }
+        }
+
+        fn check_transfer_amount(&self, asset: &AccountId, amount: u128) {
+            let (min_amount, max_amount) = self.get_transfer_limits(*asset);
+            assert!(amount >= min_amount, "Transfer amount should be bigger or equal than minimum required amount");
+            assert!(amount <= max_amount, "Transfer amount should be less or equal than maximum allowed amount");
+        }
+
+        fn check_asset_daily_limit(&mut self, asset: &AccountId, amount: u128) {
+            let asset_daily_limit: u128 = self.daily_limit.get(asset).unwrap().clone();
+
+            assert!(asset_daily_limit > 0, "Can't transfer asset without daily limit");
+
+            self.update_daily_limit(asset);
+
+            let asset_daily_spent: u128 = self.daily_spend.get(asset).unwrap().clone();
+
+            assert!(amount + asset_daily_spent <= asset_daily_limit);
+
+            self.daily_spend.insert(*asset, asset_daily_spent + amount);
+        }
+
+        //  Variants which don't have approvals anymore are dropped from the index
+        fn index_swap_variant(&mut self, source_chain_id: u64, transfer_nonce: u128, message_hash: &Vec<u8>) {
+            let key: (u64, u128) = (source_chain_id, transfer_nonce);
+            let mut variants: Vec<Vec<u8>> = Vec::new();
+            for variant in self.get_swap_variants(source_chain_id, transfer_nonce).into_iter() {
+                if variant != *message_hash && self.swap_requests.contains_key(&variant) {
+                    variants.push(variant);
+                }
+            }
+            let is_new_variant: bool = !self.swap_requests.contains_key(message_hash);
+            let is_conflict: bool = is_new_variant && !variants.is_empty();
+            variants.push(message_hash.clone());
+
+            if is_conflict {
+                if self.freeze_conflicts {
+                    self.frozen_nonces.insert(key, true);
+                }
+                self.env().emit_event(ConflictDetected {
+                    source_chain_id,
+                    transfer_nonce,
+                    message_hashes: variants.clone(),
+                    frozen: self.freeze_conflicts,
+                });
+            }
+            self.swap_variants.insert(key, variants);
+        }
+
+        fn clear_swap_requests(&mut self) {
+            self.swap_requests = StorageHashMap::default();
+            self.swap_variants = StorageHashMap::default();
+            self.frozen_nonces = StorageHashMap::default();
+            self.frozen_swaps = StorageHashMap::default();
+        }
+
+        fn is_in_hashes(&self, hashes: &Vec<Vec<u8>>, hash: &Vec<u8>) -> bool {
+            for el in hashes.iter() {
+                if el == hash {
+                    return true;
+                }
+            }
+            return false;
+        }
+
+        fn get_validators_who_approved(&self, message_hash: &Vec<u8>) -> Option<Vec<AccountId>> {
+            let validators: Option<&Vec<AccountId>> = self.swap_requests.get(message_hash);
+            match validators {
+                Some(n) => return Some(n.clone()),
+                None => return None,
+            }
+        }
+
+        fn is_in(&self, list_of_accs: &Vec<AccountId>, new_acc: &AccountId) -> bool {
+            for acc in list_of_accs.iter() {
+                if acc == new_acc {
+                    return true;
+                }
+            }
+            return false;
+        }
+
+        //  Executed nonces which are not greater than the highest contiguous nonce are removed from the storage
+        fn record_inbound_nonce(&mut self, source_chain_id: u64, transfer_nonce: u128) {
+            assert!(!self.is_nonce_executed(source_chain_id, transfer_nonce), "Transfer with this nonce was already executed");
+            let mut contiguous_nonce: u128 = self.get_highest_contiguous_nonce(source_chain_id);
+            if self.strict_nonce_ordering {
+                assert!(transfer_nonce == contiguous_nonce + 1, "Transfer nonce is out of order");
+            }
+
+            self.executed_nonces.insert((source_chain_id, transfer_nonce), true);
+            while self.executed_nonces.take(&(source_chain_id, contiguous_nonce + 1)).is_some() {
+                contiguous_nonce = contiguous_nonce + 1;
+            }
+            self.contiguous_nonces.insert(source_chain_id, contiguous_nonce);
+        }
+
+        fn increase_transfer_nonce(&mut self) {
+            self.transfer_nonce = self.transfer_nonce + 1;
+        }
+
+        fn check_asset(&self, asset_address: &AccountId) -> bool {
+            let zero_address: AccountId = AccountId::from(ZERO_ADDRESS_BYTES);
+            if asset_address == &zero_address || self.tokens.contains_key(asset_address) {
+                return true;
+            } else {
+                return false;
+            }
+        }
+
+        fn hash_message(&self, swap_message: SwapMessage) -> Vec<u8> {
+            let mut encoded: Vec<u8> = self.domain_separator();
+            match self.hash_scheme {
+                HashScheme::ScaleSha3 => encoded.extend_from_slice(swap_message.encode().as_slice()),
+                HashScheme::AbiKeccak => encoded.extend_from_slice(self.abi_encode_packed(&swap_message).as_slice()),
+            }
+            self.hash_bytes(&encoded)
+        }
+
+        //  Binds swap hashes to this bridge deployment, so approvals can't be replayed on another bridge
+        //  which has the same chain ID
+        fn domain_separator(&self) -> Vec<u8> {
+            let bridge_address: AccountId = self.env().account_id();
+            let encoded: Vec<u8> = match self.hash_scheme {
+                HashScheme::ScaleSha3 => (bridge_address, PROTOCOL_VERSION, self.domain_chain_id).encode(),
+                HashScheme::AbiKeccak => {
+                    let mut packed: Vec<u8> = Vec::new();
+                    packed.extend_from_slice(bridge_address.encode().as_slice());
+                    packed.extend_from_slice(&PROTOCOL_VERSION.to_be_bytes());
+                    packed.extend_from_slice(&self.to_uint256(self.domain_chain_id as u128));
+                    packed
+                }
+            };
+            self.hash_bytes(&encoded)
+        }
+
+        fn hash_bytes(&self, data: &[u8]) -> Vec<u8> {
+            match self.hash_scheme {
+                HashScheme::ScaleSha3 => {
+                    let mut hasher = Sha3_256::new();
+                    hasher.input(data);
+                    let result = hasher.result();
+                    result.to_vec()
+                },
+                HashScheme::AbiKeccak => {
+                    let mut hasher = Keccak256::new();
+                    hasher.input(data);
+                    let result = hasher.result();
+                    result.to_vec()
+                }
+            }
+        }
+
+        fn verify_merkle_proof(&self, leaf: &Vec<u8>, proof: &Vec<Vec<u8>>, root: &Vec<u8>) -> bool {
+            let mut computed_hash: Vec<u8> = leaf.clone();
+            for sibling in proof.iter() {
+                let mut pair: Vec<u8> = Vec::new();
+                if computed_hash <= *sibling {
+                    pair.extend_from_slice(computed_hash.as_slice());
+                    pair.extend_from_slice(sibling.as_slice());
+                } else {
+                    pair.extend_from_slice(sibling.as_slice());
+                    pair.extend_from_slice(computed_hash.as_slice());
+                }
+                computed_hash = self.hash_bytes(&pair);
+            }
+            computed_hash == *root
+        }
+
+        //  Amount is recorded without the fee charged on this side, it's the amount to release on the other chain
+        fn record_outbound_transfer(&mut self, receiver: String, sender: AccountId, amount: u128, asset: AccountId, timestamp: u64, remote_asset: Vec<u8>, remote_amount: u128) {
+            let leaf: Vec<u8> = self.compute_outbound_leaf(receiver.clone(), sender, amount, asset, self.transfer_nonce, timestamp, remote_asset.clone(), remote_amount);
+            self.append_outbound_leaf(leaf);
+            self.outbound_transfers.insert(self.transfer_nonce, OutboundTransfer {
+                receiver,
+                sender,
+                amount,
+                asset,
+                timestamp,
+                destination_chain_id: self.destination_chain_id,
+                remote_asset,
+                remote_amount,
+            });
+        }
+
+        //  Incremental Merkle tree of outbound transfers, the same as in Ethereum deposit contract.
+        //  It always uses Keccak256, so the root can be verified by contracts on the other chain
+        //  regardless of the hash scheme of swap messages.
+        fn append_outbound_leaf(&mut self, leaf: Vec<u8>) {
+            assert!(self.outbound_leaf_count < (1u64 << OUTBOUND_TREE_DEPTH) - 1, "Outbound Merkle tree is full");
+            self.outbound_leaf_count = self.outbound_leaf_count + 1;
+            let mut node: Vec<u8> = leaf;
+            let mut size: u64 = self.outbound_leaf_count;
+            for height in 0..OUTBOUND_TREE_DEPTH {
+                if size & 1 == 1 {
+                    self.outbound_branch[height] = node;
+                    return;
+                }
+                node = self.keccak_pair(&self.outbound_branch[height], &node);
+                size = size / 2;
+            }
+        }
+
+        fn keccak_pair(&self, left: &Vec<u8>, right: &Vec<u8>) -> Vec<u8> {
+            let mut hasher = Keccak256::new();
+            hasher.input(left.as_slice());
+            hasher.input(right.as_slice());
+            hasher.result().to_vec()
+        }
+
+        fn abi_encode_packed(&self, swap_message: &SwapMessage) -> Vec<u8> {
+            let mut encoded: Vec<u8> = Vec::new();
+            encoded.push(swap_message.chain_id);
+            encoded.extend_from_slice(swap_message.receiver.encode().as_slice());
+            encoded.extend_from_slice(swap_message.sender.as_bytes());
+            encoded.extend_from_slice(&swap_message.timestamp.to_be_bytes());
+            encoded.extend_from_slice(&self.to_uint256(swap_message.amount));
+            encoded.extend_from_slice(swap_message.asset.encode().as_slice());
+            encoded.extend_from_slice(&self.to_uint256(swap_message.transfer_nonce));
+            encoded.extend_from_slice(&self.to_uint256(swap_message.source_chain_id as u128));
+            encoded
+        }
+
+        fn to_uint256(&self, value: u128) -> [u8; 32] {
+            let mut word: [u8; 32] = [0; 32];
+            word[16..].copy_from_slice(&value.to_be_bytes());
+            word
+        }
+
+        //  New key can have approvals left from the time it was a validator before, in this case
+        //  approval of the old key is dropped so the validator isn't counted twice
+        fn replace_approver(approvals: &mut Vec<AccountId>, old_validator: &AccountId, new_validator: &AccountId) {
+            if approvals.contains(new_validator) {
+                approvals.retain(|approver| approver != old_validator);
+            } else {
+                for approver in approvals.iter_mut() {
+                    if approver == old_validator {
+                        *approver = *new_validator;
+                    }
+                }
+            }
+        }
+
+        fn clear_root_requests(&mut self) {
+            self.root_requests = StorageHashMap::default();
+            self.approved_roots = StorageHashMap::default();
+        }
+
+        fn check_expiration_time(&self, tx_time: u64) -> bool {
+            let current_time: u64 = self.env().block_timestamp() / 1000;
+            
+            if current_time - tx_time > self.tx_expiration_time {
+                return false;
+            } else {
+                return true;
+            }
+        }
+
+        fn update_daily_limit(&mut self, asset: &AccountId) {
+            let current_time: u64 = self.env().block_timestamp() / 1000;
+
+            let last_limit_set_time: &u64 = self.daily_limit_set_time.get(asset).unwrap();
+            if current_time - last_limit_set_time > ONE_DAY {
                 self.daily_limit_set_time.insert(asset.clone(), current_time);
                 self.daily_spend.insert(asset.clone(), 0);
             }
         }
 
-        fn make_swap(&mut self, asset: AccountId, amount: u128, receiver: AccountId) {
+        fn execute_approved_swap(&mut self, transfer_info: SwapMessage, message_hash: Vec<u8>, relayer: AccountId) {
+            self.swap_requests.take(&message_hash);
+            self.settle_swap(transfer_info, message_hash, relayer);
+        }
+
+        //  Marks swap as executed and pays it, swaps to blocked receivers are moved to quarantine
+        fn settle_swap(&mut self, transfer_info: SwapMessage, message_hash: Vec<u8>, relayer: AccountId) {
+            self.record_inbound_nonce(transfer_info.source_chain_id, transfer_info.transfer_nonce);
+            self.executed_swaps.insert(message_hash.clone(), true);
+
+            // Other variants of the executed transfer can't be executed anymore
+            if let Some(variants) = self.swap_variants.take(&(transfer_info.source_chain_id, transfer_info.transfer_nonce)) {
+                for variant in variants.iter() {
+                    if *variant != message_hash {
+                        self.swap_requests.take(variant);
+                    }
+                    self.frozen_swaps.take(variant);
+                }
+            }
+
+            if self.is_account_blocked(transfer_info.receiver) {
+                let receiver: AccountId = transfer_info.receiver;
+                self.quarantined_swaps.insert(message_hash.clone(), transfer_info);
+                self.env().emit_event(SwapQuarantined {
+                    message_hash,
+                    receiver,
+                });
+                return;
+            }
+
+            let amount: u128 = self.to_local_amount(&transfer_info);
+            self.make_swap(transfer_info.asset, amount, transfer_info.receiver, Some(relayer));
+        }
+
+        fn make_swap(&mut self, asset: AccountId, amount: u128, receiver: AccountId, relayer: Option<AccountId>) {
             let zero_address: AccountId = AccountId::from(ZERO_ADDRESS_BYTES);
             
+            let fee_amount: u128 = self.compute_fee(asset, TransferDirection::Inbound, amount, receiver);
+            let amount_to_send: u128 = amount - fee_amount;
+
+            // Released coins back transfers locked earlier, the pool and then surplus of the bridge cover the rest
+            if asset == zero_address {
+                let released_from_locked: u128 = if amount < self.locked_liquidity { amount } else { self.locked_liquidity };
+                self.locked_liquidity = self.locked_liquidity - released_from_locked;
+                let shortfall: u128 = amount - released_from_locked;
+                let pool_available: u128 = self.pool_liquidity - self.pool_borrowed;
+                self.pool_borrowed = self.pool_borrowed + if shortfall < pool_available { shortfall } else { pool_available };
+            }
+            self.collect_fee(asset, fee_amount, relayer);
+
             if asset == zero_address {
-                let amount_to_send: u128 = amount - (amount * self.fee / 100);
-                self.distribute_rewards_for_validators(amount);
+                self.ensure_native_payout_covered(amount_to_send);
                 assert!(self.env().transfer(receiver, amount_to_send).is_ok(), "Error while transfer coins to the receiver");
             } else {
-                let amount_to_send: u128 = amount - (amount * self.fee / 100);
                 assert!(self.token_contract.mint(amount_to_send, receiver), "Error while mint tokens for the receiver");
             }
         }
 
+        //  Must be called after the paid obligation was removed from accounting
+        fn ensure_native_payout_covered(&self, amount: u128) {
+            assert!(self.env().balance() >= self.native_obligations() + amount, "Payout would leave the bridge insolvent");
+        }
+
+        //  Coins lent by the pool are already paid out, so only available pool liquidity has to be in balance
+        fn native_obligations(&self) -> u128 {
+            let zero_address: AccountId = AccountId::from(ZERO_ADDRESS_BYTES);
+            self.locked_liquidity + self.accrued_rewards + self.get_treasury_balance(zero_address) + self.pool_liquidity - self.pool_borrowed
+        }
+
         fn ensure_owner(&self, caller: AccountId) {
             assert_eq!(caller, self.owner, "This method can be called only by owner");
         }
+
+        fn ensure_compliance(&self, caller: AccountId) {
+            assert!(caller == self.owner || caller == self.compliance_manager, "This method can be called only by owner or compliance manager");
+        }
+
+        fn ensure_not_blocked(&self, sender: AccountId, receiver: &String) {
+            assert!(!self.is_account_blocked(sender), "Sender is blocked");
+            assert!(!self.blocked_receivers.contains_key(&self.normalize_receiver(receiver)), "Receiver is blocked");
+        }
+
+        //  Addresses of the other chain are compared in lowercase and without 0x prefix
+        fn normalize_receiver(&self, receiver: &String) -> String {
+            let lowercase: String = receiver.to_ascii_lowercase();
+            match lowercase.strip_prefix("0x") {
+                Some(address) => String::from(address),
+                None => lowercase,
+            }
+        }
+
+        fn ensure_not_upgraded(&self) {
+            assert!(self.successor.is_none(), "Bridge was upgraded, use the successor contract");
+        }
     }
 
     #[cfg(test)]
     mod tests {
+        use super::*;
+        use ink_env::{call, test, DefaultEnvironment};
+        use ink_env::call::FromAccountId;
+        use ink_lang as ink;
+
+        fn default_accounts() -> test::DefaultAccounts<DefaultEnvironment> {
+            test::default_accounts::<DefaultEnvironment>().expect("Cannot get accounts")
+        }
+
+        fn contract_id() -> AccountId {
+            ink_env::account_id::<DefaultEnvironment>().expect("Cannot get contract id")
+        }
+
+        fn now() -> u64 {
+            ink_env::block_timestamp::<DefaultEnvironment>().expect("Cannot get block timestamp") / 1000
+        }
+
+        fn set_contract_balance(balance: Balance) {
+            test::set_account_balance::<DefaultEnvironment>(contract_id(), balance).expect("Cannot set contract balance");
+        }
+
+        fn set_sender(sender: AccountId) {
+            set_sender_with_value(sender, 0);
+        }
+
+        fn set_sender_with_value(sender: AccountId, value: Balance) {
+            let callee = ink_env::account_id::<DefaultEnvironment>().unwrap_or([0x0; 32].into());
+            test::push_execution_context::<DefaultEnvironment>(
+                sender,
+                callee,
+                1000000,
+                value,
+                test::CallData::new(call::Selector::new([0x00; 4])),
+            );
+        }
+
+        //  Bridge owned by alice with bob, charlie and django as validators and 2 of 3 threshold
+        fn create_bridge() -> EdgewareBridge {
+            let accounts = default_accounts();
+            set_sender(accounts.alice);
+            let token_contract: ERC20Token = FromAccountId::from_account_id(AccountId::from([0x07; 32]));
+            let mut bridge = EdgewareBridge::new(2, 5, 2, 1_000_000_000_000_000_000, token_contract, 1, 10);
+            bridge.add_validator(accounts.bob);
+            bridge.add_validator(accounts.charlie);
+            bridge.add_validator(accounts.django);
+            bridge
+        }
+
+        fn coin_swap_message(receiver: AccountId, amount: u128, transfer_nonce: u128) -> SwapMessage {
+            SwapMessage {
+                chain_id: 1,
+                receiver,
+                sender: String::from("0x5b38da6a701c568545dcfcb03fcb875f56beddc4"),
+                timestamp: now(),
+                amount,
+                asset: AccountId::from(ZERO_ADDRESS_BYTES),
+                transfer_nonce,
+                source_chain_id: 2,
+            }
+        }
+
+        #[ink::test]
+        fn schedule_upgrade_sets_timelock() {
+            let accounts = default_accounts();
+            let mut bridge = create_bridge();
+            bridge.schedule_upgrade(accounts.frank);
+            assert_eq!(bridge.get_pending_upgrade(), Some(PendingUpgrade {
+                successor: accounts.frank,
+                execute_after: now() + DEFAULT_UPGRADE_DELAY,
+            }));
+            bridge.cancel_upgrade();
+            assert_eq!(bridge.get_pending_upgrade(), None);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Upgrade timelock hasn't expired yet")]
+        fn execute_upgrade_fails_before_timelock() {
+            let accounts = default_accounts();
+            let mut bridge = create_bridge();
+            bridge.schedule_upgrade(accounts.frank);
+            bridge.execute_upgrade();
+        }
+
+        #[ink::test]
+        fn execute_upgrade_moves_balance_to_successor() {
+            let accounts = default_accounts();
+            let mut bridge = create_bridge();
+            //  Timelock has already expired
+            bridge.pending_upgrade = Some(PendingUpgrade {
+                successor: accounts.frank,
+                execute_after: now(),
+            });
+            set_contract_balance(10_000);
+            let minimum_balance: Balance = ink_env::minimum_balance::<DefaultEnvironment>().expect("Cannot get minimum balance");
+            let successor_balance: Balance = test::get_account_balance::<DefaultEnvironment>(accounts.frank).expect("Cannot get successor balance");
+
+            bridge.execute_upgrade();
+            assert_eq!(bridge.get_pending_upgrade(), None);
+            assert_eq!(
+                test::get_account_balance::<DefaultEnvironment>(accounts.frank).expect("Cannot get successor balance"),
+                successor_balance + 10_000 - minimum_balance
+            );
+            assert_eq!(ink_env::balance::<DefaultEnvironment>().expect("Cannot get contract balance"), minimum_balance);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Bridge was already migrated")]
+        fn migrate_can_be_called_once() {
+            let bridge = create_bridge();
+            let state: BridgeState = bridge.export_state();
+            let mut successor = create_bridge();
+            successor.migrate(state.clone());
+            successor.migrate(state);
+        }
+
+        #[ink::test]
+        fn migrate_imports_exported_state() {
+            let accounts = default_accounts();
+            let mut bridge = create_bridge();
+            bridge.remove_validator(accounts.django);
+            bridge.set_tx_expiration_time(3600);
+            let state: BridgeState = bridge.export_state();
+            assert_eq!(state.storage_version, STORAGE_VERSION);
+
+            let mut successor = create_bridge();
+            successor.migrate(state);
+            assert_eq!(successor.get_validators().len(), 2);
+            assert!(successor.is_validator_in(accounts.bob));
+            assert!(!successor.is_validator_in(accounts.django));
+            assert_eq!(successor.get_tx_expiration_time(), 3600);
+            assert_eq!(successor.get_storage_version(), STORAGE_VERSION);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Migrated state has newer storage version than the contract supports")]
+        fn migrate_rejects_newer_storage_version() {
+            let bridge = create_bridge();
+            let mut state: BridgeState = bridge.export_state();
+            state.storage_version = STORAGE_VERSION + 1;
+            let mut successor = create_bridge();
+            successor.migrate(state);
+        }
+
+        #[ink::test]
+        fn abi_keccak_uses_keccak256() {
+            let mut bridge = create_bridge();
+            bridge.set_hash_scheme(HashScheme::AbiKeccak);
+            // keccak256("") differs from sha3_256("") which was standardized later with other padding
+            let empty_keccak: [u8; 32] = [
+                0xc5, 0xd2, 0x46, 0x01, 0x86, 0xf7, 0x23, 0x3c, 0x92, 0x7e, 0x7d, 0xb2, 0xdc, 0xc7, 0x03, 0xc0,
+                0xe5, 0x00, 0xb6, 0x53, 0xca, 0x82, 0x27, 0x3b, 0x7b, 0xfa, 0xd8, 0x04, 0x5d, 0x85, 0xa4, 0x70,
+            ];
+            assert_eq!(bridge.hash_bytes(&[]), empty_keccak.to_vec());
+        }
+
+        #[ink::test]
+        fn abi_keccak_hash_matches_solidity_encode_packed() {
+            let mut bridge = create_bridge();
+            bridge.set_hash_scheme(HashScheme::AbiKeccak);
+            let transfer_info = SwapMessage {
+                chain_id: 1,
+                receiver: AccountId::from([0x11; 32]),
+                sender: String::from("0xab"),
+                timestamp: 0x0102030405060708,
+                amount: 1000,
+                asset: AccountId::from(ZERO_ADDRESS_BYTES),
+                transfer_nonce: 7,
+                source_chain_id: 2,
+            };
+
+            // keccak256(abi.encodePacked(bytes32 bridge, uint16 protocolVersion, uint256 domainChainId))
+            let mut domain: Vec<u8> = contract_id().encode();
+            domain.extend_from_slice(&[0x00, 0x01]);
+            domain.extend_from_slice(&[0x00; 31]);
+            domain.push(0x01);
+            let mut hasher = Keccak256::new();
+            hasher.input(domain.as_slice());
+            let domain_separator: Vec<u8> = hasher.result().to_vec();
+            assert_eq!(bridge.get_domain_separator(), domain_separator);
+
+            // keccak256(abi.encodePacked(domainSeparator, uint8 chainId, bytes32 receiver, string sender,
+            //     uint64 timestamp, uint256 amount, bytes32 asset, uint256 transferNonce, uint256 sourceChainId))
+            let mut packed: Vec<u8> = domain_separator;
+            packed.push(0x01);
+            packed.extend_from_slice(&[0x11; 32]);
+            packed.extend_from_slice(b"0xab");
+            packed.extend_from_slice(&[0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08]);
+            packed.extend_from_slice(&[0x00; 30]);
+            packed.extend_from_slice(&[0x03, 0xe8]);
+            packed.extend_from_slice(&[0x00; 32]);
+            packed.extend_from_slice(&[0x00; 31]);
+            packed.push(0x07);
+            packed.extend_from_slice(&[0x00; 31]);
+            packed.push(0x02);
+            assert_eq!(packed.len(), 32 + 1 + 32 + 4 + 8 + 32 + 32 + 32 + 32);
+            let mut hasher = Keccak256::new();
+            hasher.input(packed.as_slice());
+            assert_eq!(bridge.compute_swap_hash(transfer_info), hasher.result().to_vec());
+        }
+
+        #[ink::test]
+        fn swap_hash_depends_on_domain() {
+            let accounts = default_accounts();
+            let mut bridge = create_bridge();
+            let transfer_info: SwapMessage = coin_swap_message(accounts.eve, 1000, 1);
+            let domain_separator: Vec<u8> = bridge.get_domain_separator();
+            let message_hash: Vec<u8> = bridge.compute_swap_hash(transfer_info.clone());
+
+            let mut encoded: Vec<u8> = (contract_id(), PROTOCOL_VERSION, 1u64).encode();
+            let mut hasher = Sha3_256::new();
+            hasher.input(encoded.as_slice());
+            assert_eq!(domain_separator, hasher.result().to_vec());
+
+            encoded = domain_separator.clone();
+            encoded.extend_from_slice(transfer_info.encode().as_slice());
+            let mut hasher = Sha3_256::new();
+            hasher.input(encoded.as_slice());
+            assert_eq!(message_hash, hasher.result().to_vec());
+
+            bridge.set_domain_chain_id(7);
+            assert_ne!(bridge.get_domain_separator(), domain_separator);
+            assert_ne!(bridge.compute_swap_hash(transfer_info), message_hash);
+        }
+
+        #[ink::test]
+        fn set_domain_chain_id_drops_approvals() {
+            let accounts = default_accounts();
+            let mut bridge = create_bridge();
+            let transfer_info: SwapMessage = coin_swap_message(accounts.eve, 1000, 1);
+            set_sender(accounts.bob);
+            bridge.request_swap(transfer_info.clone());
+            assert_eq!(bridge.get_request_swaps().len(), 1);
+
+            set_sender(accounts.alice);
+            bridge.set_domain_chain_id(7);
+            assert_eq!(bridge.get_request_swaps().len(), 0);
+        }
+
+        //  Pairs of the batch tree are hashed in sorted order
+        fn batch_root(leaves: &Vec<Vec<u8>>) -> Vec<u8> {
+            let (left, right) = if leaves[0] <= leaves[1] { (&leaves[0], &leaves[1]) } else { (&leaves[1], &leaves[0]) };
+            let mut hasher = Sha3_256::new();
+            hasher.input(left.as_slice());
+            hasher.input(right.as_slice());
+            hasher.result().to_vec()
+        }
+
+        #[ink::test]
+        fn claim_pays_transfer_of_approved_batch() {
+            let accounts = default_accounts();
+            let mut bridge = create_bridge();
+            set_contract_balance(10_000);
+            let first: SwapMessage = coin_swap_message(accounts.eve, 1000, 1);
+            let second: SwapMessage = coin_swap_message(accounts.frank, 500, 2);
+            let mut leaves: Vec<Vec<u8>> = Vec::new();
+            leaves.push(bridge.compute_swap_hash(first.clone()));
+            leaves.push(bridge.compute_swap_hash(second.clone()));
+            let root: Vec<u8> = batch_root(&leaves);
+
+            set_sender(accounts.bob);
+            bridge.approve_root(root.clone());
+            assert!(!bridge.is_root_approved(root.clone()));
+            set_sender(accounts.charlie);
+            bridge.approve_root(root.clone());
+            assert!(bridge.is_root_approved(root.clone()));
+
+            set_sender(accounts.frank);
+            let mut proof: Vec<Vec<u8>> = Vec::new();
+            proof.push(leaves[1].clone());
+            bridge.claim(first, root, proof);
+            assert!(bridge.is_swap_executed(leaves[0].clone()));
+            assert!(!bridge.is_swap_executed(leaves[1].clone()));
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Invalid Merkle proof")]
+        fn claim_rejects_invalid_proof() {
+            let accounts = default_accounts();
+            let mut bridge = create_bridge();
+            set_contract_balance(10_000);
+            let transfer_info: SwapMessage = coin_swap_message(accounts.eve, 1000, 1);
+            let root: Vec<u8> = [0x01; 32].to_vec();
+            set_sender(accounts.bob);
+            bridge.approve_root(root.clone());
+            set_sender(accounts.charlie);
+            bridge.approve_root(root.clone());
+
+            bridge.claim(transfer_info, root, Vec::new());
+        }
+
+        #[ink::test]
+        fn outbound_root_commits_to_transfers() {
+            let accounts = default_accounts();
+            let mut bridge = create_bridge();
+            bridge.set_fee(0);
+            let zero_address: AccountId = AccountId::from(ZERO_ADDRESS_BYTES);
+            let receiver: String = String::from("0x5b38da6a701c568545dcfcb03fcb875f56beddc4");
+
+            set_sender_with_value(accounts.eve, 1000);
+            assert!(bridge.transfer_coin(receiver.clone()));
+            assert_eq!(bridge.get_outbound_leaf_count(), 1);
+
+            let mut node: Vec<u8> = bridge.compute_outbound_leaf(receiver, accounts.eve, 1000, zero_address, 1, now(), Vec::new(), 1000);
+            let mut zero_hash: Vec<u8> = ZERO_ADDRESS_BYTES.to_vec();
+            for _ in 0..OUTBOUND_TREE_DEPTH {
+                node = bridge.keccak_pair(&node, &zero_hash);
+                zero_hash = bridge.keccak_pair(&zero_hash, &zero_hash);
+            }
+            assert_eq!(bridge.get_outbound_root(), node);
+        }
+
+        //  Sends approvals of bob and charlie, which is enough for the default threshold
+        fn approve_swap(bridge: &mut EdgewareBridge, transfer_info: SwapMessage) {
+            let accounts = default_accounts();
+            set_sender(accounts.bob);
+            bridge.request_swap(transfer_info.clone());
+            set_sender(accounts.charlie);
+            bridge.request_swap(transfer_info);
+            set_sender(accounts.alice);
+        }
+
+        #[ink::test]
+        fn treasury_gets_its_share_of_fees() {
+            let accounts = default_accounts();
+            let mut bridge = create_bridge();
+            let zero_address: AccountId = AccountId::from(ZERO_ADDRESS_BYTES);
+            set_contract_balance(10_000);
+            bridge.set_treasury(accounts.frank);
+            bridge.set_treasury_share(50);
+
+            approve_swap(&mut bridge, coin_swap_message(accounts.eve, 1000, 1));
+            assert_eq!(bridge.get_treasury_balance(zero_address), 10);
+            assert_eq!(bridge.get_validator_rewards(accounts.bob), 3);
+
+            set_sender(accounts.frank);
+            bridge.withdraw_treasury(zero_address);
+            assert_eq!(bridge.get_treasury_balance(zero_address), 0);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Only treasury can withdraw treasury fees")]
+        fn withdraw_treasury_is_only_for_treasury() {
+            let accounts = default_accounts();
+            let mut bridge = create_bridge();
+            bridge.set_treasury(accounts.frank);
+            set_sender(accounts.eve);
+            bridge.withdraw_treasury(AccountId::from(ZERO_ADDRESS_BYTES));
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Bridge was upgraded, use the successor contract")]
+        fn withdraw_treasury_fails_after_upgrade() {
+            let accounts = default_accounts();
+            let mut bridge = create_bridge();
+            bridge.set_treasury(accounts.frank);
+            bridge.treasury_balances.insert(AccountId::from(ZERO_ADDRESS_BYTES), 100);
+            bridge.successor = Some(accounts.django);
+            set_sender(accounts.frank);
+            bridge.withdraw_treasury(AccountId::from(ZERO_ADDRESS_BYTES));
+        }
+
+        #[ink::test]
+        fn executed_nonces_are_tracked_per_source_chain() {
+            let accounts = default_accounts();
+            let mut bridge = create_bridge();
+            set_contract_balance(10_000);
+            approve_swap(&mut bridge, coin_swap_message(accounts.eve, 100, 1));
+            approve_swap(&mut bridge, coin_swap_message(accounts.eve, 100, 3));
+            assert_eq!(bridge.get_highest_contiguous_nonce(2), 1);
+            assert!(bridge.is_nonce_executed(2, 3));
+            assert!(!bridge.is_nonce_executed(2, 2));
+            assert!(!bridge.is_nonce_executed(5, 1));
+
+            approve_swap(&mut bridge, coin_swap_message(accounts.eve, 100, 2));
+            assert_eq!(bridge.get_highest_contiguous_nonce(2), 3);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Transfer nonce is out of order")]
+        fn strict_nonce_ordering_rejects_gaps() {
+            let accounts = default_accounts();
+            let mut bridge = create_bridge();
+            set_contract_balance(10_000);
+            bridge.set_strict_nonce_ordering(true);
+            approve_swap(&mut bridge, coin_swap_message(accounts.eve, 100, 2));
+        }
+
+        #[ink::test]
+        fn migrate_keeps_executed_nonces() {
+            let accounts = default_accounts();
+            let mut bridge = create_bridge();
+            set_contract_balance(10_000);
+            approve_swap(&mut bridge, coin_swap_message(accounts.eve, 100, 1));
+            approve_swap(&mut bridge, coin_swap_message(accounts.eve, 100, 3));
+
+            let mut successor = create_bridge();
+            successor.migrate(bridge.export_state());
+            assert_eq!(successor.get_highest_contiguous_nonce(2), 1);
+            assert!(successor.is_nonce_executed(2, 3));
+            assert!(!successor.is_nonce_executed(2, 2));
+        }
+
+        #[ink::test]
+        fn outbound_transfers_are_queryable_by_nonce() {
+            let accounts = default_accounts();
+            let mut bridge = create_bridge();
+            bridge.set_fee(0);
+            bridge.set_destination_chain_id(2);
+            let receiver: String = String::from("0x5b38da6a701c568545dcfcb03fcb875f56beddc4");
+            set_sender_with_value(accounts.eve, 1000);
+            bridge.transfer_coin(receiver.clone());
+            set_sender_with_value(accounts.frank, 500);
+            bridge.transfer_coin(receiver.clone());
+
+            assert_eq!(bridge.get_outbound_transfer(1), Some(OutboundTransfer {
+                receiver,
+                sender: accounts.eve,
+                amount: 1000,
+                asset: AccountId::from(ZERO_ADDRESS_BYTES),
+                timestamp: now(),
+                destination_chain_id: 2,
+                remote_asset: Vec::new(),
+                remote_amount: 1000,
+            }));
+            let transfers: Vec<(u128, OutboundTransfer)> = bridge.get_outbound_transfers(0, 10);
+            assert_eq!(transfers.len(), 2);
+            assert_eq!(transfers[1].0, 2);
+            assert_eq!(transfers[1].1.sender, accounts.frank);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Requested range of nonces is too big")]
+        fn outbound_transfers_query_is_limited() {
+            let bridge = create_bridge();
+            bridge.get_outbound_transfers(1, MAX_QUERY_RANGE + 1);
+        }
+
+        #[ink::test]
+        fn relayer_gets_bonus_for_executed_swap() {
+            let accounts = default_accounts();
+            let mut bridge = create_bridge();
+            set_contract_balance(10_000);
+            bridge.set_relayer_bonus(50);
+            bridge.set_public_relaying(true);
+            let transfer_info: SwapMessage = coin_swap_message(accounts.eve, 1000, 1);
+            let message_hash: Vec<u8> = bridge.compute_swap_hash(transfer_info.clone());
+
+            approve_swap(&mut bridge, transfer_info.clone());
+            assert!(!bridge.is_swap_executed(message_hash.clone()));
+
+            set_sender(accounts.frank);
+            bridge.execute_swap(transfer_info);
+            assert!(bridge.is_swap_executed(message_hash));
+            assert_eq!(bridge.get_validator_rewards(accounts.frank), 10);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Public relaying is disabled")]
+        fn execute_swap_requires_public_relaying() {
+            let accounts = default_accounts();
+            let mut bridge = create_bridge();
+            set_sender(accounts.frank);
+            bridge.execute_swap(coin_swap_message(accounts.eve, 1000, 1));
+        }
+
+        #[ink::test]
+        fn rotate_validator_keeps_rewards_and_approvals() {
+            let accounts = default_accounts();
+            let mut bridge = create_bridge();
+            let transfer_info: SwapMessage = coin_swap_message(accounts.eve, 1000, 1);
+            let message_hash: Vec<u8> = bridge.compute_swap_hash(transfer_info.clone());
+            bridge.validator_rewards.insert(accounts.bob, 5);
+            set_sender(accounts.bob);
+            bridge.request_swap(transfer_info);
+
+            bridge.rotate_validator(accounts.bob, accounts.frank);
+            assert!(!bridge.is_validator_in(accounts.bob));
+            assert!(bridge.is_validator_in(accounts.frank));
+            assert_eq!(bridge.get_validator_rewards(accounts.frank), 5);
+            assert_eq!(bridge.get_validators_who_approved(&message_hash), Some([accounts.frank].to_vec()));
+        }
+
+        #[ink::test]
+        fn rotate_validator_doesnt_count_approval_twice() {
+            let accounts = default_accounts();
+            let mut bridge = create_bridge();
+            let transfer_info: SwapMessage = coin_swap_message(accounts.eve, 1000, 1);
+            let message_hash: Vec<u8> = bridge.compute_swap_hash(transfer_info.clone());
+            bridge.set_threshold(3);
+            bridge.add_validator(accounts.frank);
+            set_sender(accounts.frank);
+            bridge.request_swap(transfer_info.clone());
+            set_sender(accounts.bob);
+            bridge.request_swap(transfer_info);
+            set_sender(accounts.alice);
+            bridge.remove_validator(accounts.frank);
+
+            bridge.rotate_validator(accounts.bob, accounts.frank);
+            assert_eq!(bridge.get_count_of_approvals(message_hash), 1);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Validator already exists")]
+        fn add_validator_rejects_existing_validator() {
+            let accounts = default_accounts();
+            let mut bridge = create_bridge();
+            bridge.suspend_validator(accounts.bob);
+            bridge.add_validator(accounts.bob);
+        }
+
+        #[ink::test]
+        fn suspended_validator_approval_isnt_counted() {
+            let accounts = default_accounts();
+            let mut bridge = create_bridge();
+            set_contract_balance(10_000);
+            let transfer_info: SwapMessage = coin_swap_message(accounts.eve, 1000, 1);
+            let message_hash: Vec<u8> = bridge.compute_swap_hash(transfer_info.clone());
+            set_sender(accounts.bob);
+            bridge.request_swap(transfer_info.clone());
+            set_sender(accounts.alice);
+            bridge.suspend_validator(accounts.bob);
+            assert_eq!(bridge.get_validator_info(accounts.bob).unwrap().status, ValidatorStatus::Suspended);
+
+            set_sender(accounts.charlie);
+            bridge.request_swap(transfer_info.clone());
+            assert!(!bridge.is_swap_executed(message_hash.clone()));
+            set_sender(accounts.django);
+            bridge.request_swap(transfer_info);
+            assert!(bridge.is_swap_executed(message_hash));
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Count of Validators can't be less than necessary threshold of approvals")]
+        fn suspension_cant_drop_active_validators_below_threshold() {
+            let accounts = default_accounts();
+            let mut bridge = create_bridge();
+            bridge.suspend_validator(accounts.bob);
+            bridge.suspend_validator(accounts.charlie);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Count of Validators can't be less than necessary threshold of approvals")]
+        fn removal_counts_only_active_validators() {
+            let accounts = default_accounts();
+            let mut bridge = create_bridge();
+            bridge.suspend_validator(accounts.bob);
+            bridge.remove_validator(accounts.charlie);
+        }
+
+        #[ink::test]
+        fn migrate_keeps_validator_records() {
+            let accounts = default_accounts();
+            let mut bridge = create_bridge();
+            bridge.set_validator_name(accounts.bob, String::from("bob"));
+            bridge.suspend_validator(accounts.charlie);
+
+            let mut successor = create_bridge();
+            successor.migrate(bridge.export_state());
+            assert_eq!(successor.get_validator_info(accounts.bob).unwrap().name, String::from("bob"));
+            assert_eq!(successor.get_validator_info(accounts.charlie).unwrap().status, ValidatorStatus::Suspended);
+        }
+
+        #[ink::test]
+        fn validator_activity_is_recorded() {
+            let accounts = default_accounts();
+            let mut bridge = create_bridge();
+            bridge.set_liveness_period(ONE_DAY);
+            set_sender(accounts.bob);
+            bridge.request_swap(coin_swap_message(accounts.eve, 1000, 1));
+            bridge.heartbeat();
+
+            let validator_info: ValidatorInfo = bridge.get_validator_info(accounts.bob).unwrap();
+            assert_eq!(validator_info.approvals_count, 1);
+            assert_eq!(validator_info.last_seen, now());
+            assert!(bridge.is_validator_live(accounts.bob));
+            assert!(!bridge.is_validator_live(accounts.eve));
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Only Validator can send heartbeat")]
+        fn heartbeat_is_only_for_validators() {
+            let accounts = default_accounts();
+            let mut bridge = create_bridge();
+            set_sender(accounts.eve);
+            bridge.heartbeat();
+        }
+
+        #[ink::test]
+        fn quorum_ratio_follows_active_validators() {
+            let accounts = default_accounts();
+            let mut bridge = create_bridge();
+            bridge.add_validator(accounts.eve);
+            bridge.set_quorum_ratio(2, 3);
+            assert_eq!(bridge.get_signature_threshold(), 3);
+
+            bridge.suspend_validator(accounts.eve);
+            bridge.suspend_validator(accounts.django);
+            assert_eq!(bridge.get_signature_threshold(), 2);
+
+            bridge.set_quorum_ratio(0, 1);
+            bridge.reactivate_validator(accounts.eve);
+            bridge.reactivate_validator(accounts.django);
+            assert_eq!(bridge.get_signature_threshold(), 2);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Quorum ratio must be less than one")]
+        fn quorum_ratio_must_be_less_than_one() {
+            let mut bridge = create_bridge();
+            bridge.set_quorum_ratio(3, 3);
+        }
+
+        #[ink::test]
+        fn asset_fee_is_bounded_by_min_and_max_fee() {
+            let accounts = default_accounts();
+            let mut bridge = create_bridge();
+            let zero_address: AccountId = AccountId::from(ZERO_ADDRESS_BYTES);
+            bridge.set_asset_fee(zero_address, TransferDirection::Inbound, 50, 10, 100);
+            assert_eq!(bridge.compute_fee(zero_address, TransferDirection::Inbound, 1000, accounts.eve), 10);
+            assert_eq!(bridge.compute_fee(zero_address, TransferDirection::Inbound, 10_000, accounts.eve), 50);
+            assert_eq!(bridge.compute_fee(zero_address, TransferDirection::Inbound, 100_000, accounts.eve), 100);
+            assert_eq!(bridge.compute_fee(zero_address, TransferDirection::Inbound, 5, accounts.eve), 5);
+
+            bridge.remove_asset_fee(zero_address, TransferDirection::Inbound);
+            assert_eq!(bridge.get_asset_fee(zero_address, TransferDirection::Inbound), bridge.get_default_fee());
+        }
+
+        #[ink::test]
+        fn quote_fee_returns_fee_and_net_amount() {
+            let mut bridge = create_bridge();
+            let zero_address: AccountId = AccountId::from(ZERO_ADDRESS_BYTES);
+            bridge.set_fee_side(FeeSide::Source);
+            assert_eq!(bridge.quote_fee(zero_address, 1000), (20, 980));
+
+            bridge.set_asset_fee(zero_address, TransferDirection::Outbound, 0, 0, 0);
+            assert_eq!(bridge.quote_fee(zero_address, 1000), (0, 1000));
+        }
+
+        #[ink::test]
+        fn migrate_keeps_fee_schedule() {
+            let mut bridge = create_bridge();
+            let zero_address: AccountId = AccountId::from(ZERO_ADDRESS_BYTES);
+            bridge.set_default_fee(250, 5, 1000);
+            bridge.set_asset_fee(zero_address, TransferDirection::Inbound, 50, 10, 100);
+
+            let mut successor = create_bridge();
+            successor.migrate(bridge.export_state());
+            assert_eq!(successor.get_default_fee(), bridge.get_default_fee());
+            assert_eq!(
+                successor.get_asset_fee(zero_address, TransferDirection::Inbound),
+                bridge.get_asset_fee(zero_address, TransferDirection::Inbound)
+            );
+            assert_eq!(successor.get_asset_fee(zero_address, TransferDirection::Outbound), bridge.get_default_fee());
+        }
+
+        #[ink::test]
+        fn get_fee_truncates_basis_points() {
+            let mut bridge = create_bridge();
+            bridge.set_default_fee(250, 0, u128::MAX);
+            assert_eq!(bridge.get_fee(), 2);
+            assert_eq!(bridge.get_default_fee().fee_bps, 250);
+        }
+
+        #[ink::test]
+        fn asset_transfer_limits_override_minimum_amount() {
+            let mut bridge = create_bridge();
+            let zero_address: AccountId = AccountId::from(ZERO_ADDRESS_BYTES);
+            assert_eq!(bridge.get_transfer_limits(zero_address), (10, u128::MAX));
+            bridge.set_transfer_limits(zero_address, 100, 1000);
+            assert_eq!(bridge.get_transfer_limits(zero_address), (100, 1000));
+            bridge.remove_transfer_limits(zero_address);
+            assert_eq!(bridge.get_transfer_limits(zero_address), (10, u128::MAX));
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Transfer amount should be less or equal than maximum allowed amount")]
+        fn transfer_above_maximum_amount_fails() {
+            let accounts = default_accounts();
+            let mut bridge = create_bridge();
+            bridge.set_transfer_limits(AccountId::from(ZERO_ADDRESS_BYTES), 100, 1000);
+            set_sender_with_value(accounts.eve, 1001);
+            bridge.transfer_coin(String::from("0x5b38da6a701c568545dcfcb03fcb875f56beddc4"));
+        }
+
+        #[ink::test]
+        fn outbound_amount_is_scaled_to_remote_decimals() {
+            let accounts = default_accounts();
+            let mut bridge = create_bridge();
+            let zero_address: AccountId = AccountId::from(ZERO_ADDRESS_BYTES);
+            bridge.set_fee(0);
+            bridge.set_destination_chain_id(5);
+            bridge.register_remote_asset(zero_address, 5, b"ETH".to_vec(), 18, 6);
+
+            set_sender_with_value(accounts.eve, 3_000_000_000_123);
+            bridge.transfer_coin(String::from("0x5b38da6a701c568545dcfcb03fcb875f56beddc4"));
+            let transfer: OutboundTransfer = bridge.get_outbound_transfer(1).unwrap();
+            assert_eq!(transfer.amount, 3_000_000_000_000);
+            assert_eq!(transfer.remote_asset, b"ETH".to_vec());
+            assert_eq!(transfer.remote_amount, 3);
+            // Dust below precision of the remote asset is kept as fee
+            assert_eq!(bridge.get_validator_rewards(accounts.bob), 41);
+        }
+
+        #[ink::test]
+        fn inbound_amount_is_scaled_to_local_decimals() {
+            let accounts = default_accounts();
+            let mut bridge = create_bridge();
+            let zero_address: AccountId = AccountId::from(ZERO_ADDRESS_BYTES);
+            bridge.register_remote_asset(zero_address, 2, b"ETH".to_vec(), 18, 6);
+            assert_eq!(bridge.to_local_amount(&coin_swap_message(accounts.eve, 2, 1)), 2_000_000_000_000);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Transfer amount is too small for the destination asset")]
+        fn outbound_amount_below_remote_precision_fails() {
+            let accounts = default_accounts();
+            let mut bridge = create_bridge();
+            let zero_address: AccountId = AccountId::from(ZERO_ADDRESS_BYTES);
+            bridge.set_destination_chain_id(5);
+            bridge.register_remote_asset(zero_address, 5, b"ETH".to_vec(), 18, 6);
+            set_sender_with_value(accounts.eve, 999_999_999_999);
+            bridge.transfer_coin(String::from("0x5b38da6a701c568545dcfcb03fcb875f56beddc4"));
+        }
+
+        #[ink::test]
+        fn quote_fee_includes_dust() {
+            let mut bridge = create_bridge();
+            let zero_address: AccountId = AccountId::from(ZERO_ADDRESS_BYTES);
+            bridge.set_destination_chain_id(5);
+            bridge.register_remote_asset(zero_address, 5, b"ETH".to_vec(), 18, 6);
+            assert_eq!(bridge.quote_fee(zero_address, 3_000_000_000_123), (123, 3_000_000_000_000));
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Fee is bigger than maximum fee set by the sender")]
+        fn transfer_with_max_fee_counts_dust() {
+            let accounts = default_accounts();
+            let mut bridge = create_bridge();
+            let zero_address: AccountId = AccountId::from(ZERO_ADDRESS_BYTES);
+            bridge.set_destination_chain_id(5);
+            bridge.register_remote_asset(zero_address, 5, b"ETH".to_vec(), 18, 6);
+            set_sender_with_value(accounts.eve, 3_000_000_000_123);
+            bridge.transfer_coin_with_max_fee(String::from("0x5b38da6a701c568545dcfcb03fcb875f56beddc4"), 100);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Receiver is blocked")]
+        fn transfer_to_blocked_receiver_fails_regardless_of_case() {
+            let accounts = default_accounts();
+            let mut bridge = create_bridge();
+            bridge.block_receiver(String::from("0x5B38Da6a701c568545dCfcB03FcB875f56beddC4"));
+            assert!(bridge.is_receiver_blocked(String::from("5b38da6a701c568545dcfcb03fcb875f56beddc4")));
+            set_sender_with_value(accounts.eve, 1000);
+            bridge.transfer_coin(String::from("0x5b38da6a701c568545dcfcb03fcb875f56beddc4"));
+        }
+
+        #[ink::test]
+        fn swap_to_blocked_account_is_quarantined() {
+            let accounts = default_accounts();
+            let mut bridge = create_bridge();
+            set_contract_balance(10_000);
+            let transfer_info: SwapMessage = coin_swap_message(accounts.eve, 1000, 1);
+            let message_hash: Vec<u8> = bridge.compute_swap_hash(transfer_info.clone());
+            bridge.block_account(accounts.eve);
+
+            approve_swap(&mut bridge, transfer_info.clone());
+            assert!(bridge.is_swap_executed(message_hash.clone()));
+            assert_eq!(bridge.get_quarantined_swap(message_hash.clone()), Some(transfer_info));
+
+            bridge.unblock_account(accounts.eve);
+            bridge.release_quarantined_swap(message_hash.clone());
+            assert_eq!(bridge.get_quarantined_swap(message_hash), None);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Bridge was upgraded, use the successor contract")]
+        fn quarantined_swap_cant_be_released_after_upgrade() {
+            let accounts = default_accounts();
+            let mut bridge = create_bridge();
+            set_contract_balance(10_000);
+            let transfer_info: SwapMessage = coin_swap_message(accounts.eve, 1000, 1);
+            let message_hash: Vec<u8> = bridge.compute_swap_hash(transfer_info.clone());
+            bridge.block_account(accounts.eve);
+            approve_swap(&mut bridge, transfer_info);
+
+            bridge.unblock_account(accounts.eve);
+            bridge.successor = Some(accounts.frank);
+            bridge.release_quarantined_swap(message_hash);
+        }
+
+        #[ink::test]
+        fn migrate_keeps_blocklists_and_quarantine() {
+            let accounts = default_accounts();
+            let mut bridge = create_bridge();
+            set_contract_balance(10_000);
+            let transfer_info: SwapMessage = coin_swap_message(accounts.eve, 1000, 1);
+            let message_hash: Vec<u8> = bridge.compute_swap_hash(transfer_info.clone());
+            bridge.block_account(accounts.eve);
+            bridge.block_receiver(String::from("0xABCD"));
+            approve_swap(&mut bridge, transfer_info);
+
+            let mut successor = create_bridge();
+            successor.migrate(bridge.export_state());
+            assert!(successor.is_account_blocked(accounts.eve));
+            assert!(successor.is_receiver_blocked(String::from("abcd")));
+            assert!(successor.get_quarantined_swap(message_hash).is_some());
+        }
+
+        #[ink::test]
+        fn fee_tiers_reduce_fee() {
+            let accounts = default_accounts();
+            let mut bridge = create_bridge();
+            let zero_address: AccountId = AccountId::from(ZERO_ADDRESS_BYTES);
+            bridge.set_fee_tier(accounts.eve, FeeTier::Discount(2500));
+            bridge.set_fee_tier(accounts.frank, FeeTier::Exempt);
+            assert_eq!(bridge.compute_fee(zero_address, TransferDirection::Inbound, 1000, accounts.django), 20);
+            assert_eq!(bridge.compute_fee(zero_address, TransferDirection::Inbound, 1000, accounts.eve), 15);
+            assert_eq!(bridge.compute_fee(zero_address, TransferDirection::Inbound, 1000, accounts.frank), 0);
+
+            bridge.remove_fee_tier(accounts.eve);
+            assert_eq!(bridge.get_fee_tier(accounts.eve), None);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Discount should be between 0 and 10000 basis points")]
+        fn full_discount_is_rejected() {
+            let accounts = default_accounts();
+            let mut bridge = create_bridge();
+            bridge.set_fee_tier(accounts.eve, FeeTier::Discount(10000));
+        }
+
+        #[ink::test]
+        fn transfer_with_max_fee_accepts_current_fee() {
+            let accounts = default_accounts();
+            let mut bridge = create_bridge();
+            bridge.set_fee_side(FeeSide::Source);
+            set_sender_with_value(accounts.eve, 1000);
+            assert!(bridge.transfer_coin_with_max_fee(String::from("0x5b38da6a701c568545dcfcb03fcb875f56beddc4"), 20));
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Fee is bigger than maximum fee set by the sender")]
+        fn transfer_with_max_fee_rejects_raised_fee() {
+            let accounts = default_accounts();
+            let mut bridge = create_bridge();
+            bridge.set_fee_side(FeeSide::Source);
+            bridge.set_fee(3);
+            set_sender_with_value(accounts.eve, 1000);
+            bridge.transfer_coin_with_max_fee(String::from("0x5b38da6a701c568545dcfcb03fcb875f56beddc4"), 20);
+        }
+
+        #[ink::test]
+        fn outbound_transfers_are_free_by_default() {
+            let accounts = default_accounts();
+            let bridge = create_bridge();
+            set_sender(accounts.eve);
+            assert_eq!(bridge.quote_fee(AccountId::from(ZERO_ADDRESS_BYTES), 1000), (0, 1000));
+        }
+
+        #[ink::test]
+        fn source_side_fee_is_deducted_from_outbound_amount() {
+            let accounts = default_accounts();
+            let mut bridge = create_bridge();
+            let zero_address: AccountId = AccountId::from(ZERO_ADDRESS_BYTES);
+            bridge.set_fee_side(FeeSide::Source);
+            assert_eq!(bridge.compute_fee(zero_address, TransferDirection::Inbound, 1000, accounts.eve), 0);
+
+            set_sender_with_value(accounts.eve, 1000);
+            bridge.transfer_coin(String::from("0x5b38da6a701c568545dcfcb03fcb875f56beddc4"));
+            let transfer: OutboundTransfer = bridge.get_outbound_transfer(1).unwrap();
+            assert_eq!(transfer.amount, 980);
+            assert_eq!(transfer.remote_amount, 980);
+        }
+
+        #[ink::test]
+        fn solvency_report_tracks_locked_liquidity() {
+            let accounts = default_accounts();
+            let mut bridge = create_bridge();
+            set_sender_with_value(accounts.eve, 1000);
+            bridge.transfer_coin(String::from("0x5b38da6a701c568545dcfcb03fcb875f56beddc4"));
+
+            set_contract_balance(1500);
+            let report: SolvencyReport = bridge.get_solvency_report();
+            assert_eq!(report.locked_liquidity, 1000);
+            assert_eq!(report.surplus, 500);
+            assert_eq!(report.deficit, 0);
+
+            set_contract_balance(400);
+            assert_eq!(bridge.get_solvency_report().deficit, 600);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Payout would leave the bridge insolvent")]
+        fn rewards_payout_cant_use_locked_liquidity() {
+            let accounts = default_accounts();
+            let mut bridge = create_bridge();
+            set_sender_with_value(accounts.eve, 1000);
+            bridge.transfer_coin(String::from("0x5b38da6a701c568545dcfcb03fcb875f56beddc4"));
+            bridge.validator_rewards.insert(accounts.bob, 100);
+            bridge.accrued_rewards = 100;
+
+            set_contract_balance(1000);
+            set_sender(accounts.bob);
+            bridge.request_rewards();
+        }
+
+        #[ink::test]
+        fn liquidity_providers_get_proportional_shares() {
+            let accounts = default_accounts();
+            let mut bridge = create_bridge();
+            set_sender_with_value(accounts.eve, 1000);
+            assert_eq!(bridge.provide_liquidity(), 1000);
+            set_sender_with_value(accounts.frank, 500);
+            assert_eq!(bridge.provide_liquidity(), 500);
+            assert_eq!(bridge.get_total_lp_shares(), 1500);
+            assert_eq!(bridge.get_pool_liquidity(), (1500, 1500));
+
+            set_contract_balance(1500);
+            set_sender(accounts.eve);
+            assert_eq!(bridge.withdraw_liquidity(1000), 1000);
+            assert_eq!(bridge.get_lp_shares(accounts.eve), 0);
+            assert_eq!(bridge.get_pool_liquidity(), (500, 500));
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Liquidity shares calculation overflows")]
+        fn provide_liquidity_reports_overflow() {
+            let accounts = default_accounts();
+            let mut bridge = create_bridge();
+            set_sender_with_value(accounts.eve, u128::MAX / 2);
+            bridge.provide_liquidity();
+            set_sender_with_value(accounts.frank, 3);
+            bridge.provide_liquidity();
+        }
+
+        #[ink::test]
+        fn migrate_keeps_liquidity_pool_and_treasury() {
+            let accounts = default_accounts();
+            let mut bridge = create_bridge();
+            let zero_address: AccountId = AccountId::from(ZERO_ADDRESS_BYTES);
+            set_sender_with_value(accounts.eve, 1000);
+            bridge.provide_liquidity();
+            bridge.treasury_balances.insert(zero_address, 100);
+
+            set_contract_balance(3000);
+            let mut successor = create_bridge();
+            successor.migrate(bridge.export_state());
+            assert_eq!(successor.get_lp_shares(accounts.eve), 1000);
+            assert_eq!(successor.get_pool_liquidity(), (1000, 1000));
+            assert_eq!(successor.get_treasury_balance(zero_address), 100);
+            let report: SolvencyReport = successor.get_solvency_report();
+            assert_eq!(report.locked_liquidity, 1900);
+            assert_eq!(report.deficit, 0);
+        }
+
+        #[ink::test]
+        fn validator_can_revoke_approval() {
+            let accounts = default_accounts();
+            let mut bridge = create_bridge();
+            let transfer_info: SwapMessage = coin_swap_message(accounts.eve, 1000, 1);
+            let message_hash: Vec<u8> = bridge.compute_swap_hash(transfer_info.clone());
+            set_sender(accounts.bob);
+            bridge.request_swap(transfer_info.clone());
+            assert_eq!(bridge.get_count_of_approvals(message_hash.clone()), 1);
+
+            set_sender(accounts.bob);
+            bridge.revoke_approval(message_hash.clone());
+            assert!(!bridge.is_swap_request_in(message_hash.clone()));
+
+            set_sender(accounts.charlie);
+            bridge.request_swap(transfer_info);
+            assert!(!bridge.is_swap_executed(message_hash.clone()));
+            assert_eq!(bridge.get_count_of_approvals(message_hash), 1);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "This Validator hasn't approved the swap")]
+        fn revoke_requires_approval() {
+            let accounts = default_accounts();
+            let mut bridge = create_bridge();
+            let transfer_info: SwapMessage = coin_swap_message(accounts.eve, 1000, 1);
+            let message_hash: Vec<u8> = bridge.compute_swap_hash(transfer_info.clone());
+            set_sender(accounts.bob);
+            bridge.request_swap(transfer_info);
+            set_sender(accounts.charlie);
+            bridge.revoke_approval(message_hash);
+        }
+
+        #[ink::test]
+        fn conflicting_messages_are_detected() {
+            let accounts = default_accounts();
+            let mut bridge = create_bridge();
+            set_sender(accounts.bob);
+            bridge.request_swap(coin_swap_message(accounts.eve, 1000, 1));
+            set_sender(accounts.charlie);
+            bridge.request_swap(coin_swap_message(accounts.eve, 2000, 1));
+            assert_eq!(bridge.get_swap_variants(2, 1).len(), 2);
+            assert!(!bridge.is_nonce_frozen(2, 1));
+        }
+
+        #[ink::test]
+        fn accepted_variant_is_executed_on_resolution() {
+            let accounts = default_accounts();
+            let mut bridge = create_bridge();
+            set_contract_balance(10_000);
+            bridge.set_freeze_conflicts(true);
+            let accepted: SwapMessage = coin_swap_message(accounts.eve, 1000, 1);
+            let accepted_hash: Vec<u8> = bridge.compute_swap_hash(accepted.clone());
+            let rejected_hash: Vec<u8> = bridge.compute_swap_hash(coin_swap_message(accounts.eve, 2000, 1));
+
+            set_sender(accounts.bob);
+            bridge.request_swap(accepted.clone());
+            set_sender(accounts.charlie);
+            bridge.request_swap(coin_swap_message(accounts.eve, 2000, 1));
+            assert!(bridge.is_nonce_frozen(2, 1));
+            set_sender(accounts.django);
+            bridge.request_swap(accepted);
+            assert!(!bridge.is_swap_executed(accepted_hash.clone()));
+
+            set_sender(accounts.alice);
+            bridge.resolve_conflict(2, 1, Some(accepted_hash.clone()));
+            assert!(bridge.is_swap_executed(accepted_hash));
+            assert!(!bridge.is_swap_request_in(rejected_hash));
+            assert!(!bridge.is_nonce_frozen(2, 1));
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Accepted hash isn't a variant of this transfer")]
+        fn resolve_conflict_rejects_unknown_variant() {
+            let accounts = default_accounts();
+            let mut bridge = create_bridge();
+            set_sender(accounts.bob);
+            bridge.request_swap(coin_swap_message(accounts.eve, 1000, 1));
+            set_sender(accounts.alice);
+            bridge.resolve_conflict(2, 1, Some([0x01; 32].to_vec()));
+        }
+
+        fn create_weighted_bridge() -> EdgewareBridge {
+            let accounts = default_accounts();
+            let mut bridge = create_bridge();
+            bridge.set_validator_weight(accounts.bob, 3);
+            bridge.set_weight_threshold(4);
+            bridge
+        }
+
+        #[ink::test]
+        fn swap_is_executed_when_approvers_weight_reaches_threshold() {
+            let accounts = default_accounts();
+            let mut bridge = create_weighted_bridge();
+            set_contract_balance(10_000);
+            let transfer_info: SwapMessage = coin_swap_message(accounts.eve, 1000, 1);
+            let message_hash: Vec<u8> = bridge.compute_swap_hash(transfer_info.clone());
+
+            set_sender(accounts.charlie);
+            bridge.request_swap(transfer_info.clone());
+            set_sender(accounts.django);
+            bridge.request_swap(transfer_info.clone());
+            assert!(!bridge.is_swap_executed(message_hash.clone()));
+            assert_eq!(bridge.get_approved_weight(message_hash.clone()), 2);
+
+            set_sender(accounts.bob);
+            bridge.request_swap(transfer_info);
+            assert!(bridge.is_swap_executed(message_hash));
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Weight threshold can't be greater than total weight of active validators")]
+        fn weight_threshold_cant_exceed_active_weight() {
+            let mut bridge = create_weighted_bridge();
+            bridge.set_weight_threshold(6);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Weight of active validators can't be less than weight threshold")]
+        fn suspension_cant_make_weight_threshold_unreachable() {
+            let accounts = default_accounts();
+            let mut bridge = create_weighted_bridge();
+            bridge.suspend_validator(accounts.bob);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Weight of active validators can't be less than weight threshold")]
+        fn exit_cant_make_weight_threshold_unreachable() {
+            let accounts = default_accounts();
+            let mut bridge = create_weighted_bridge();
+            set_sender(accounts.bob);
+            bridge.announce_exit();
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "Weight of active validators can't be less than weight threshold")]
+        fn removal_cant_make_weight_threshold_unreachable() {
+            let accounts = default_accounts();
+            let mut bridge = create_weighted_bridge();
+            bridge.remove_validator(accounts.bob);
+        }
+
+        #[ink::test]
+        fn set_hash_scheme_drops_root_approvals() {
+            let accounts = default_accounts();
+            let mut bridge = create_bridge();
+            let root: Vec<u8> = [0x01; 32].to_vec();
+            set_sender(accounts.bob);
+            bridge.approve_root(root.clone());
+            set_sender(accounts.charlie);
+            bridge.approve_root([0x02; 32].to_vec());
+            bridge.approve_root(root.clone());
+            assert!(bridge.is_root_approved(root.clone()));
+
+            set_sender(accounts.alice);
+            bridge.set_hash_scheme(HashScheme::AbiKeccak);
+            assert!(!bridge.is_root_approved(root));
+            assert_eq!(bridge.get_count_of_root_approvals([0x02; 32].to_vec()), 0);
+        }
     }
 }
@@ -24,6 +24,26 @@ function sleepAsync(ms) {
     return new Promise(resolve => setTimeout(resolve, ms));
   }
 
+function domainSeparator() {
+    let registry = new polkaTypes.TypeRegistry();
+
+    const DomainType = polkaTypes.Struct.with({
+        bridge: polkaTypes.GenericAccountId,
+        protocol_version: polkaTypes.u16,
+        domain_chain_id: polkaTypes.u64
+    });
+
+    const domain = new DomainType(registry, {
+        bridge: process.env.BRIDGE_ADDRESS,
+        protocol_version: 1,
+        domain_chain_id: process.env.CHAIN_ID
+    });
+
+    const hash = new sha3.SHA3(256);
+    hash.update(Buffer.from(domain.toU8a()));
+    return hash.digest();
+}
+
 function hashSwapMessageStruct(swap_data) {
     let registry = new polkaTypes.TypeRegistry();
 
@@ -34,13 +54,14 @@ function hashSwapMessageStruct(swap_data) {
         timestamp: polkaTypes.u64,
         amount: polkaTypes.u128,
         asset: polkaTypes.GenericAccountId,
-        transfer_nonce: polkaTypes.u128
+        transfer_nonce: polkaTypes.u128,
+        source_chain_id: polkaTypes.u64
     });
 
     const tmp = new CustomType(registry, swap_data);
 
     const hash = new sha3.SHA3(256);
-    hash.update(Buffer.from(tmp.toU8a()));
+    hash.update(Buffer.concat([domainSeparator(), Buffer.from(tmp.toU8a())]));
     return hash.digest().toJSON().data;
 }
 
@@ -119,7 +140,8 @@ describe('Bridge', function() {
                 timestamp: currentTime,
                 amount: transferAmount,
                 asset: '',
-                transfer_nonce: 19
+                transfer_nonce: 19,
+                source_chain_id: process.env.SOURCE_CHAIN_ID
             }
 
             let tx = await bridgeContract.tx.requestSwap(0, -1, swapMessage);
@@ -139,7 +161,8 @@ describe('Bridge', function() {
                 timestamp: currentTime,
                 amount: transferAmount,
                 asset: '',
-                transfer_nonce: 19
+                transfer_nonce: 19,
+                source_chain_id: process.env.SOURCE_CHAIN_ID
             }
 
             let tx = await bridgeContract.tx.requestSwap(0, -1, swapMessage);
@@ -159,7 +182,8 @@ describe('Bridge', function() {
                 timestamp: currentTime,
                 amount: transferAmount,
                 asset: '',
-                transfer_nonce: 19
+                transfer_nonce: 19,
+                source_chain_id: process.env.SOURCE_CHAIN_ID
             }
 
             let balance = await api.query.system.account(keyring.addFromUri('//Ferdie').address);
@@ -185,7 +209,8 @@ describe('Bridge', function() {
                 timestamp: currentTime,
                 amount: transferAmount,
                 asset: '',
-                transfer_nonce: 6
+                transfer_nonce: 6,
+                source_chain_id: process.env.SOURCE_CHAIN_ID
             };
 
             let balance = await api.query.system.account(keyring.addFromUri('//Eve').address);
@@ -220,7 +245,8 @@ describe('Bridge', function() {
                 timestamp: currentTime,
                 amount: transferAmount,
                 asset: '',
-                transfer_nonce: 4
+                transfer_nonce: 4,
+                source_chain_id: process.env.SOURCE_CHAIN_ID
             };
 
             let validators = [keyring.addFromUri('//Alice'), keyring.addFromUri('//Bob'), keyring.addFromUri('//Eve')];  // Eve here isn't a validator
@@ -257,12 +283,25 @@ describe('Bridge', function() {
         it('7_check fee after coin transfer', async function() {
             this.timeout(50000);
 
-            let tx = await bridgeContract.tx.transferCoin(10000, -1, "asdfasdfsaf");
-            let _ = await tx.signAndSend(keyring.addFromUri('//Eve'));
+            // Outbound transfers are free by default, fee is charged by the destination side
+            let tx = await bridgeContract.tx.setFeeSide(0, -1, 'Source');
+            let _ = await tx.signAndSend(keyring.addFromUri('//Alice'));
+
+            await sleepAsync(6000);
+
+            let result = await bridgeContract.query.quoteFee(keyring.addFromUri('//Eve').address, 0, -1, '', 10000);
+            let quote = result.result.toHuman().Ok.data;
+            let feeAmount = littleEndToHex(quote.slice(2, 34));
+            let netAmount = littleEndToHex(quote.slice(34));
+            assert.strictEqual(feeAmount, 10000 * process.env.CONTRACT_TRANSFER_FEE / 100);
+            assert.strictEqual(netAmount, 10000 - feeAmount);
+
+            tx = await bridgeContract.tx.transferCoin(10000, -1, "asdfasdfsaf");
+            _ = await tx.signAndSend(keyring.addFromUri('//Eve'));
 
             await sleepAsync(6000);
 
-            let result = await bridgeContract.query.getValidatorRewards(keyring.addFromUri('//Alice').address, 0, -1, keyring.addFromUri('//Alice').address);
+            result = await bridgeContract.query.getValidatorRewards(keyring.addFromUri('//Alice').address, 0, -1, keyring.addFromUri('//Alice').address);
             let rewardsAmount = littleEndToHex(result.result.toHuman().Ok.data.slice(2));
             console.log(`Rewards: ${rewardsAmount}`);
 
@@ -277,6 +316,11 @@ describe('Bridge', function() {
             rewardsAmount = littleEndToHex(result.result.toHuman().Ok.data.slice(2));
 
             assert.strictEqual(rewardsAmount == 0, true);
+
+            tx = await bridgeContract.tx.setFeeSide(0, -1, 'Destination');
+            _ = await tx.signAndSend(keyring.addFromUri('//Alice'));
+
+            await sleepAsync(6000);
         });
         it('8_try to send more than set limit', async function() {
             this.timeout(50000);
@@ -308,7 +352,8 @@ describe('Bridge', function() {
                 timestamp: currentTime,
                 amount: transferAmount,
                 asset: '',
-                transfer_nonce: 10
+                transfer_nonce: 10,
+                source_chain_id: process.env.SOURCE_CHAIN_ID
             };
 
             let validators = [keyring.addFromUri('//Alice'), keyring.addFromUri('//Bob'), keyring.addFromUri('//Charlie')];
@@ -347,7 +392,8 @@ describe('Bridge', function() {
                 timestamp: currentTime,
                 amount: amountToTransfer,
                 asset: process.env.TOKEN_ADDRESS,
-                transfer_nonce: 3
+                transfer_nonce: 3,
+                source_chain_id: process.env.SOURCE_CHAIN_ID
             }
 
             let result = await tokenContract.query.balanceOf(keyring.addFromUri('//Alice').address, 0, -1, keyring.addFromUri('//Ferdie').address);
@@ -378,7 +424,8 @@ describe('Bridge', function() {
                 timestamp: currentTime,
                 amount: 1000000000000000000n,
                 asset: process.env.TOKEN_ADDRESS,
-                transfer_nonce: 1
+                transfer_nonce: 1,
+                source_chain_id: process.env.SOURCE_CHAIN_ID
             };
 
             let result = await tokenContract.query.balanceOf(keyring.addFromUri('//Alice').address, 0, -1, keyring.addFromUri('//Eve').address);
@@ -415,7 +462,8 @@ describe('Bridge', function() {
                 timestamp: currentTime,
                 amount: amountToTransfer,
                 asset: process.env.TOKEN_ADDRESS,
-                transfer_nonce: 77094
+                transfer_nonce: 77094,
+                source_chain_id: process.env.SOURCE_CHAIN_ID
             };
 
             let hashedMessage = hashSwapMessageStruct(swapMessage);